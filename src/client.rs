@@ -0,0 +1,568 @@
+use crate::{
+    link::Link,
+    product_details::ProductSellers,
+    reviews::{ProductReviews, Review, ReviewsQuery},
+    search::{SearchQuery, SearchResult},
+    Error, Fetcher, Page, ProductDetails, ProductRef, ProductSearch, RateLimiter, ReqwestFetcher,
+    Result, RetryPolicy,
+};
+use futures_util::{stream, Stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::{fmt, future::Future, sync::Arc, time::Duration};
+use url::Url;
+
+#[derive(Clone)]
+/// Reusable client to scrape Flipkart.
+///
+/// The client owns the `Fetcher` used to get the webpages, by default
+/// a `ReqwestFetcher`, so connections and cookies are shared between
+/// all the requests made with it.
+/// Cloning the client is cheap and clones share the same fetcher.
+///
+/// Failed requests are retried according to the client's `RetryPolicy`,
+/// and every request, including retries, waits for the client's
+/// `RateLimiter` if one is set.
+///
+/// ```rust,no_run
+/// use std::error::Error;
+/// use flipkart_scraper::FlipkartClient;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     let client = FlipkartClient::new()?;
+///     let search = client.search("samsung washing machine").await?;
+///     for result in search.iter().take(3) {
+///         let details = result.fetch_product_with(&client).await?;
+///         println!("{:#?}", details);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct FlipkartClient {
+    fetcher: Arc<dyn Fetcher>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    pincode: Option<String>,
+}
+
+impl fmt::Debug for FlipkartClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlipkartClient")
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("pincode", &self.pincode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FlipkartClient {
+    /// Creates a client with the default configuration.
+    pub fn new() -> Result<Self> {
+        Self::builder().build()
+    }
+
+    /// Returns a builder to configure the client.
+    pub fn builder() -> FlipkartClientBuilder {
+        FlipkartClientBuilder::default()
+    }
+
+    /// Returns a clone of the client which requests product and sellers
+    /// pages for delivery to the given pincode.
+    ///
+    /// The pincode is sent as the `pincode` parameter of the pages, which
+    /// Flipkart doesn't document and may ignore, so it is only a hint: delivery
+    /// details are only returned when the page shows they are for the
+    /// pincode, otherwise `ProductDetails::delivery` is `None` and the
+    /// sellers' delivery estimates are left out. The parameter is removed
+    /// from the URLs reported back, like `ProductDetails::final_url`.
+    ///
+    /// ```rust,no_run
+    /// use std::error::Error;
+    /// use flipkart_scraper::FlipkartClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = FlipkartClient::new()?;
+    ///     for pincode in ["110001", "400001", "560001"] {
+    ///         let details = client
+    ///             .with_pincode(pincode)
+    ///             .product_by_pid("MOBGENJWBPFYJSFT")
+    ///             .await?;
+    ///         println!("{pincode}: {:#?}", details.delivery);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_pincode(&self, pincode: impl Into<String>) -> Self {
+        Self {
+            pincode: Some(pincode.into()),
+            ..self.clone()
+        }
+    }
+
+    /// Searchs the query for a product on Flipkart.
+    ///
+    /// Only fetches the page of results selected by the query,
+    /// use `search_stream` to walk through all the pages.
+    pub async fn search(&self, query: impl Into<SearchQuery>) -> Result<ProductSearch> {
+        let query = query.into();
+        let page = self.fetch_page(&query.url()?).await?;
+        ProductSearch::from_html(query, &page.body)
+    }
+
+    /// Searchs the query and yields its pages of results one after another,
+    /// starting from the page selected by the query.
+    ///
+    /// Stops when a page has no results, after the last page if the total
+    /// number of pages is known, after `max_pages` pages if given, or after
+    /// the first error.
+    pub fn search_pages(
+        &self,
+        query: impl Into<SearchQuery>,
+        max_pages: Option<u32>,
+    ) -> impl Stream<Item = Result<ProductSearch>> {
+        let (client, query) = (self.clone(), query.into());
+        paginate(query.page, max_pages, move |page| {
+            let (client, query) = (client.clone(), query.clone().page(page));
+            async move { client.search(query).await }
+        })
+    }
+
+    /// Searchs the query and yields the results from all of its pages,
+    /// see `search_pages` for when the stream stops.
+    ///
+    /// ```rust,no_run
+    /// use flipkart_scraper::FlipkartClient;
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = FlipkartClient::new()?;
+    ///     let results = client.search_stream("samsung washing machine", Some(5));
+    ///     pin_mut!(results);
+    ///     while let Some(result) = results.next().await {
+    ///         println!("{:#?}", result?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn search_stream(
+        &self,
+        query: impl Into<SearchQuery>,
+        max_pages: Option<u32>,
+    ) -> impl Stream<Item = Result<SearchResult>> {
+        self.search_pages(query, max_pages)
+            .flat_map(|search| stream::iter(flatten_page(search.map(|search| search.results))))
+    }
+
+    /// Fetches all the sellers of a product.
+    ///
+    /// The sellers page needs the product ID, if it is missing
+    /// the product page is fetched first to find it.
+    pub async fn sellers(&self, product: &ProductRef) -> Result<ProductSellers> {
+        let product = match product.pid {
+            Some(_) => product.clone(),
+            None => {
+                let details = self.product_by_ref(product).await?;
+                let pid = details.product_id.ok_or(Error::ProductNotFound)?;
+                ProductRef {
+                    pid: Some(pid),
+                    ..product.clone()
+                }
+            }
+        };
+        let page = self
+            .fetch_page(&self.localize(&product.sellers_url()))
+            .await?;
+        let mut sellers = ProductSellers::from_html(product, &page.body)?;
+        // estimates are for another location unless the page shows the pincode
+        if self.pincode.is_some() && sellers.pincode != self.pincode {
+            for listing in sellers.iter_mut() {
+                listing.delivery = None;
+            }
+        }
+        Ok(sellers)
+    }
+
+    /// Fetches a page of reviews of a product, by default the first page
+    /// of the most helpful reviews.
+    pub async fn reviews(&self, query: impl Into<ReviewsQuery>) -> Result<ProductReviews> {
+        let query = query.into();
+        let page = self.fetch_page(&query.url()).await?;
+        ProductReviews::from_html(query, &page.body)
+    }
+
+    /// Yields the pages of reviews of a product one after another,
+    /// starting from the page selected by the query.
+    ///
+    /// Stops when a page has no reviews, after the last page if the total
+    /// number of pages is known, after `max_pages` pages if given, or after
+    /// the first error.
+    pub fn reviews_pages(
+        &self,
+        query: impl Into<ReviewsQuery>,
+        max_pages: Option<u32>,
+    ) -> impl Stream<Item = Result<ProductReviews>> {
+        let (client, query) = (self.clone(), query.into());
+        paginate(query.page, max_pages, move |page| {
+            let (client, query) = (client.clone(), query.clone().page(page));
+            async move { client.reviews(query).await }
+        })
+    }
+
+    /// Yields the reviews of a product from all of the pages,
+    /// see `reviews_pages` for when the stream stops.
+    ///
+    /// ```rust,no_run
+    /// use flipkart_scraper::reviews::{ReviewSort, ReviewsQuery};
+    /// use flipkart_scraper::{FlipkartClient, ProductRef};
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use std::error::Error;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let client = FlipkartClient::new()?;
+    ///     let query = ReviewsQuery::new(ProductRef::from_pid("MOBGENJWBPFYJSFT"))
+    ///         .sort(ReviewSort::MostRecent);
+    ///     let reviews = client.reviews_stream(query, Some(10));
+    ///     pin_mut!(reviews);
+    ///     while let Some(review) = reviews.next().await {
+    ///         println!("{:#?}", review?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn reviews_stream(
+        &self,
+        query: impl Into<ReviewsQuery>,
+        max_pages: Option<u32>,
+    ) -> impl Stream<Item = Result<Review>> {
+        self.reviews_pages(query, max_pages)
+            .flat_map(|reviews| stream::iter(flatten_page(reviews.map(|reviews| reviews.reviews))))
+    }
+
+    /// Fetches details of the product from the given url.
+    pub async fn product(&self, url: Url) -> Result<ProductDetails> {
+        ProductDetails::fetch_with(self, url).await
+    }
+
+    /// Fetches details of the product with the given product ID.
+    pub async fn product_by_pid(&self, pid: impl Into<String>) -> Result<ProductDetails> {
+        self.product_by_ref(&ProductRef::from_pid(pid)).await
+    }
+
+    /// Fetches details of the referenced product.
+    pub async fn product_by_ref(&self, product: &ProductRef) -> Result<ProductDetails> {
+        self.product(product.canonical_url()).await
+    }
+
+    /// Resolves a link to Flipkart into the `https://www.flipkart.com` URL
+    /// it points to.
+    ///
+    /// Mobile (`m.flipkart.com`) and deep (`dl.flipkart.com`) links are
+    /// rewritten directly, while short links (like `fkrt.it`) are requested
+    /// to follow their redirects.
+    pub async fn resolve_url(&self, url: Url) -> Result<Url> {
+        match Link::parse(&url)? {
+            Link::Page(url) => Ok(url),
+            Link::Short(url) => Ok(self.retry(|| self.fetch_link(&url)).await?.url),
+        }
+    }
+
+    /// Fetches the webpage a link to Flipkart points to, without retrying,
+    /// the returned page has the normalized URL after following redirects.
+    ///
+    /// The client's pincode is added to the link, short links
+    /// are followed first and then requested again with the pincode.
+    /// The returned URLs don't have the pincode.
+    pub(crate) async fn fetch_link(&self, url: &Url) -> Result<Page> {
+        let link = Link::parse(url)?;
+        let request = match &link {
+            Link::Page(url) => self.localize(url),
+            Link::Short(url) => url.to_owned(),
+        };
+        let mut page = self.try_fetch_page(&request).await?;
+        page.url = match Link::parse(&page.url)? {
+            Link::Page(url) => url,
+            Link::Short(url) => return Err(Error::NotFlipkartUrl(url.into())),
+        };
+        if matches!(link, Link::Short(_)) && self.pincode.is_some() {
+            let mut redirects = std::mem::take(&mut page.redirects);
+            redirects.push(page.url.to_owned());
+            page = self.try_fetch_page(&self.localize(&page.url)).await?;
+            redirects.append(&mut page.redirects);
+            page.redirects = redirects;
+        }
+        page.url = self.delocalize(&page.url);
+        for redirect in &mut page.redirects {
+            *redirect = self.delocalize(redirect);
+        }
+        Ok(page)
+    }
+
+    /// Pincode the client requests delivery details for, if set.
+    pub(crate) fn pincode(&self) -> Option<&str> {
+        self.pincode.as_deref()
+    }
+
+    /// Adds the client's pincode, if set, to the url of a product page.
+    fn localize(&self, url: &Url) -> Url {
+        let mut url = url.to_owned();
+        if let Some(pincode) = &self.pincode {
+            url.query_pairs_mut().append_pair("pincode", pincode);
+        }
+        url
+    }
+
+    /// Removes the pincode added by `localize` from the url.
+    fn delocalize(&self, url: &Url) -> Url {
+        let mut url = url.to_owned();
+        if self.pincode.is_some() {
+            let pairs: Vec<_> = url
+                .query_pairs()
+                .filter(|(key, _)| key != "pincode")
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect();
+            if pairs.is_empty() {
+                url.set_query(None);
+            } else {
+                url.query_pairs_mut().clear().extend_pairs(pairs);
+            }
+        }
+        url
+    }
+
+    /// Fetches the webpage at given url using the client's fetcher,
+    /// retrying according to the client's retry policy.
+    pub(crate) async fn fetch_page(&self, url: &Url) -> Result<Page> {
+        self.retry(|| self.try_fetch_page(url)).await
+    }
+
+    /// Runs the operation, retrying it according to the client's retry policy.
+    pub(crate) async fn retry<T, F, Fut>(&self, operation: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let error = match operation().await {
+                Ok(result) => return Ok(result),
+                Err(error) => error,
+            };
+            match self.retry_policy.retry_after(attempt, &error) {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(error),
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Fetches the webpage once, turning server error responses into errors.
+    async fn try_fetch_page(&self, url: &Url) -> Result<Page> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+        let page = self.fetcher.fetch(url).await?;
+        if page.status == 429 || page.status >= 500 {
+            if page.body.contains("Internal Server Error") {
+                return Err(Error::Blocked);
+            }
+            return Err(Error::Status(page.status));
+        }
+        Ok(page)
+    }
+}
+
+/// Page of results which can be walked through by `paginate`.
+pub(crate) trait Paginated {
+    /// Total number of pages, if known.
+    fn total_pages(&self) -> Option<u32>;
+    /// Whether the page has no results.
+    fn is_empty(&self) -> bool;
+}
+
+/// Fetches the pages one after another, starting from `first_page`.
+///
+/// Stops when a page has no results, after the last page if the total
+/// number of pages is known, after `max_pages` pages if given, or after
+/// the first error.
+fn paginate<P, F, Fut>(
+    first_page: u32,
+    max_pages: Option<u32>,
+    fetch: F,
+) -> impl Stream<Item = Result<P>>
+where
+    P: Paginated,
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = Result<P>>,
+{
+    let state = (Some(first_page), max_pages, fetch);
+    stream::unfold(state, |(page, max_pages, fetch)| async move {
+        let page = page?;
+        if max_pages == Some(0) {
+            return None;
+        }
+        let results = match fetch(page).await {
+            Ok(results) if results.is_empty() => return None,
+            Ok(results) => results,
+            Err(error) => return Some((Err(error), (None, None, fetch))),
+        };
+        let next_page = Some(page + 1).filter(|next_page| {
+            results
+                .total_pages()
+                .map_or(true, |total| *next_page <= total)
+        });
+        let max_pages = max_pages.map(|pages| pages - 1);
+        Some((Ok(results), (next_page, max_pages, fetch)))
+    })
+}
+
+/// Turns a page of results into the results to yield from a stream.
+fn flatten_page<T>(page: Result<Vec<T>>) -> Vec<Result<T>> {
+    match page {
+        Ok(results) => results.into_iter().map(Ok).collect(),
+        Err(error) => vec![Err(error)],
+    }
+}
+
+/// Builder for `FlipkartClient`.
+///
+/// Use `FlipkartClient::builder` to create one.
+pub struct FlipkartClientBuilder {
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+    fetcher: Option<Arc<dyn Fetcher>>,
+    retry_policy: RetryPolicy,
+    rate_limit: Option<(f64, u32)>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    pincode: Option<String>,
+}
+
+impl fmt::Debug for FlipkartClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlipkartClientBuilder")
+            .field("headers", &self.headers)
+            .field("timeout", &self.timeout)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limit", &self.rate_limit)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("pincode", &self.pincode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for FlipkartClientBuilder {
+    fn default() -> Self {
+        Self {
+            headers: crate::build_headers(),
+            timeout: None,
+            fetcher: None,
+            retry_policy: RetryPolicy::default(),
+            rate_limit: None,
+            rate_limiter: None,
+            pincode: None,
+        }
+    }
+}
+
+impl FlipkartClientBuilder {
+    /// Sets the user agent sent with every request.
+    pub fn user_agent(self, user_agent: HeaderValue) -> Self {
+        self.header(reqwest::header::USER_AGENT, user_agent)
+    }
+
+    /// Sets a header sent with every request,
+    /// replacing the default value if there is one.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Sets the timeout for each request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the fetcher used to get the webpages.
+    ///
+    /// Headers and timeout set on the builder only apply to the
+    /// default `ReqwestFetcher` and are ignored when a fetcher is set.
+    pub fn fetcher(mut self, fetcher: impl Fetcher + 'static) -> Self {
+        self.fetcher = Some(Arc::new(fetcher));
+        self
+    }
+
+    /// Sets the policy used to retry failed requests.
+    ///
+    /// Use `RetryPolicy::none()` to disable retries.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Limits the client to `requests_per_second` requests on average,
+    /// with bursts of up to `burst` requests.
+    ///
+    /// See `RateLimiter` for details, `build` fails with
+    /// `Error::InvalidConfig` unless `requests_per_second` is
+    /// a positive finite number.
+    pub fn rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.rate_limit = Some((requests_per_second, burst));
+        self.rate_limiter = None;
+        self
+    }
+
+    /// Sets the rate limiter used by the client, the limiter
+    /// can be shared with other clients.
+    pub fn rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limit = None;
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Sets the pincode products are delivered to, which decides
+    /// their availability and delivery details.
+    ///
+    /// See `FlipkartClient::with_pincode` for how the pincode is used.
+    pub fn pincode(mut self, pincode: impl Into<String>) -> Self {
+        self.pincode = Some(pincode.into());
+        self
+    }
+
+    /// Builds the client.
+    pub fn build(self) -> Result<FlipkartClient> {
+        let rate_limiter = match self.rate_limit {
+            Some((requests_per_second, _)) if !RateLimiter::is_valid_rate(requests_per_second) => {
+                return Err(Error::InvalidConfig(format!(
+                    "rate limit of {requests_per_second} requests per second is not a positive finite number"
+                )));
+            }
+            Some((requests_per_second, burst)) => {
+                Some(Arc::new(RateLimiter::new(requests_per_second, burst)))
+            }
+            None => self.rate_limiter,
+        };
+        let fetcher = match self.fetcher {
+            Some(fetcher) => fetcher,
+            None => {
+                let mut http = reqwest::Client::builder()
+                    .default_headers(self.headers)
+                    .redirect(reqwest::redirect::Policy::none())
+                    .cookie_store(true);
+                if let Some(timeout) = self.timeout {
+                    http = http.timeout(timeout);
+                }
+                Arc::new(ReqwestFetcher::new(http.build()?))
+            }
+        };
+        Ok(FlipkartClient {
+            fetcher,
+            retry_policy: self.retry_policy,
+            rate_limiter,
+            pincode: self.pincode,
+        })
+    }
+}
@@ -0,0 +1,85 @@
+/// Errors that can occur while scraping Flipkart.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The given link could not be parsed as a URL.
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    /// The URL does not point to Flipkart.
+    #[error("Only flipkart.com is supported, got `{0}`")]
+    NotFlipkartUrl(String),
+    /// The search query has filters Flipkart can't search with.
+    #[error("Invalid search query: {0}")]
+    InvalidQuery(String),
+    /// The page says the product has been moved, deleted or never existed.
+    #[error("Link provided doesn't corresponds to any product")]
+    ProductNotFound,
+    /// Flipkart responded with its error page, it is either down
+    /// or blocking the requests.
+    #[error("Internal Server Error. Host is down or is blocking use of this library.")]
+    Blocked,
+    /// Flipkart responded with a server error or asked to slow down.
+    #[error("Flipkart responded with HTTP status {0}")]
+    Status(u16),
+    /// Flipkart kept redirecting the request.
+    #[error("Too many redirects")]
+    TooManyRedirects,
+    /// The request could not be completed.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The client was configured with invalid settings.
+    #[error("Invalid client configuration: {0}")]
+    InvalidConfig(String),
+    /// The page was fetched but its contents could not be understood.
+    #[error("Failed to parse page: {0}")]
+    Parse(String),
+}
+
+/// Kind of an `Error`, without any of its details.
+///
+/// Used to select which errors a `RetryPolicy` retries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// See `Error::InvalidUrl`.
+    InvalidUrl,
+    /// See `Error::NotFlipkartUrl`.
+    NotFlipkartUrl,
+    /// See `Error::InvalidQuery`.
+    InvalidQuery,
+    /// See `Error::ProductNotFound`.
+    ProductNotFound,
+    /// See `Error::Blocked`.
+    Blocked,
+    /// See `Error::Status`.
+    Status,
+    /// See `Error::TooManyRedirects`.
+    TooManyRedirects,
+    /// See `Error::Http`.
+    Http,
+    /// See `Error::InvalidConfig`.
+    InvalidConfig,
+    /// See `Error::Parse`.
+    Parse,
+}
+
+impl Error {
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::InvalidUrl(_) => ErrorKind::InvalidUrl,
+            Error::NotFlipkartUrl(_) => ErrorKind::NotFlipkartUrl,
+            Error::InvalidQuery(_) => ErrorKind::InvalidQuery,
+            Error::ProductNotFound => ErrorKind::ProductNotFound,
+            Error::Blocked => ErrorKind::Blocked,
+            Error::Status(_) => ErrorKind::Status,
+            Error::TooManyRedirects => ErrorKind::TooManyRedirects,
+            Error::Http(_) => ErrorKind::Http,
+            Error::InvalidConfig(_) => ErrorKind::InvalidConfig,
+            Error::Parse(_) => ErrorKind::Parse,
+        }
+    }
+}
+
+/// Result type returned by this crate.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,106 @@
+use crate::{link::Link, Error, Result};
+use async_trait::async_trait;
+use url::Url;
+
+#[derive(Debug, Clone)]
+/// A webpage returned by a `Fetcher`.
+pub struct Page {
+    /// URL the body was fetched from, after following redirects.
+    pub url: Url,
+    /// URLs which redirected to the page, starting with the requested one.
+    /// Empty if the request was not redirected.
+    pub redirects: Vec<Url>,
+    /// HTTP status code of the response.
+    pub status: u16,
+    /// Body of the webpage.
+    pub body: String,
+}
+
+#[async_trait]
+/// Transport used to get webpages from Flipkart.
+///
+/// `FlipkartClient` uses `ReqwestFetcher` by default, implement this trait
+/// to serve pages from somewhere else, like a directory of saved pages,
+/// a cache or a mock server.
+///
+/// ```rust
+/// use flipkart_scraper::{async_trait, Fetcher, FlipkartClient, Page, Result, Url};
+///
+/// struct Offline;
+///
+/// #[async_trait]
+/// impl Fetcher for Offline {
+///     async fn fetch(&self, url: &Url) -> Result<Page> {
+///         Ok(Page {
+///             url: url.clone(),
+///             redirects: Vec::new(),
+///             status: 200,
+///             body: String::from("<html></html>"),
+///         })
+///     }
+/// }
+///
+/// let client = FlipkartClient::builder().fetcher(Offline).build();
+/// ```
+pub trait Fetcher: Send + Sync {
+    /// Fetches the webpage at the given url.
+    async fn fetch(&self, url: &Url) -> Result<Page>;
+}
+
+/// Maximum number of redirects followed by `ReqwestFetcher`.
+const MAX_REDIRECTS: usize = 10;
+
+#[derive(Debug, Clone)]
+/// `Fetcher` which gets the webpages over HTTP using `reqwest`.
+///
+/// Redirects are followed by the fetcher itself to record them, so the
+/// `reqwest` client should be built with `redirect::Policy::none()`.
+/// If the client follows redirects on its own, only the final URL is known.
+///
+/// Redirects leaving Flipkart fail with `Error::NotFlipkartUrl`,
+/// and more than 10 redirects fail with `Error::TooManyRedirects`.
+pub struct ReqwestFetcher {
+    client: reqwest::Client,
+}
+
+impl ReqwestFetcher {
+    /// Creates a fetcher from the given `reqwest` client.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Fetcher for ReqwestFetcher {
+    async fn fetch(&self, url: &Url) -> Result<Page> {
+        let mut url = url.to_owned();
+        let mut redirects = Vec::new();
+        loop {
+            let webpage = self.client.get(url.to_owned()).send().await?;
+            let status = webpage.status();
+            let location = webpage
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| webpage.url().join(location).ok());
+
+            match location {
+                Some(location) if status.is_redirection() => {
+                    if redirects.len() == MAX_REDIRECTS {
+                        return Err(Error::TooManyRedirects);
+                    }
+                    Link::parse(&location)?;
+                    redirects.push(std::mem::replace(&mut url, location));
+                }
+                _ => {
+                    return Ok(Page {
+                        url: webpage.url().to_owned(),
+                        redirects,
+                        status: status.as_u16(),
+                        body: webpage.text().await?,
+                    })
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt;
+use url::Url;
+
+/// Width and height requested by `ProductImage::max_quality`, larger than
+/// any image on the CDN, which serves images no larger than their original size.
+const MAX_SIZE: u32 = 2000;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+/// Image of a product on Flipkart's CDN.
+///
+/// Image URLs look like
+/// `https://rukminim2.flixcart.com/image/<width>/<height>/<path>?q=<quality>`,
+/// so the same image can be requested at any size.
+///
+/// ```rust
+/// use flipkart_scraper::ProductImage;
+///
+/// let image = ProductImage::from(
+///     "https://rukminim2.flixcart.com/image/128/128/xif0q/mobile/k/p/n/galaxy-f13.jpeg?q=70",
+/// );
+/// assert_eq!(
+///     image.resized(832, 832),
+///     "https://rukminim2.flixcart.com/image/832/832/xif0q/mobile/k/p/n/galaxy-f13.jpeg?q=70",
+/// );
+/// assert_eq!(
+///     image.max_quality(),
+///     "https://rukminim2.flixcart.com/image/2000/2000/xif0q/mobile/k/p/n/galaxy-f13.jpeg?q=100",
+/// );
+/// ```
+pub struct ProductImage {
+    /// URL of the image as it appears in the page.
+    pub url: String,
+}
+
+impl ProductImage {
+    /// URL of the image resized to fit the given width and height.
+    ///
+    /// Returns the original URL if it is not a CDN URL with a size in it.
+    pub fn resized(&self, width: u32, height: u32) -> String {
+        self.rewrite(width, height, None)
+    }
+
+    /// URL of the image at its largest size and highest quality.
+    ///
+    /// Returns the original URL if it is not a CDN URL with a size in it.
+    pub fn max_quality(&self) -> String {
+        self.rewrite(MAX_SIZE, MAX_SIZE, Some(100))
+    }
+
+    fn rewrite(&self, width: u32, height: u32, quality: Option<u8>) -> String {
+        let Ok(mut url) = Url::parse(&self.url) else {
+            return self.url.clone();
+        };
+        if !url
+            .domain()
+            .is_some_and(|domain| domain.ends_with("flixcart.com"))
+        {
+            return self.url.clone();
+        }
+        let mut segments: Vec<_> = match url.path_segments() {
+            Some(segments) => segments.map(String::from).collect(),
+            None => return self.url.clone(),
+        };
+        // path is `/image/<width>/<height>/<path of the image>`
+        if segments.len() < 4 || segments[0] != "image" {
+            return self.url.clone();
+        }
+        segments[1] = width.to_string();
+        segments[2] = height.to_string();
+        url.set_path(&segments.join("/"));
+
+        if let Some(quality) = quality {
+            let params: Vec<(String, String)> = url
+                .query_pairs()
+                .filter(|(key, _)| key != "q")
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect();
+            url.query_pairs_mut()
+                .clear()
+                .extend_pairs(params)
+                .append_pair("q", &quality.to_string());
+        }
+        url.into()
+    }
+}
+
+impl From<String> for ProductImage {
+    fn from(url: String) -> Self {
+        ProductImage { url }
+    }
+}
+
+impl From<&str> for ProductImage {
+    fn from(url: &str) -> Self {
+        url.to_string().into()
+    }
+}
+
+impl fmt::Display for ProductImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_urls_not_on_the_cdn() {
+        let image = ProductImage::from("https://example.com/image/128/128/a.jpeg?q=70");
+        assert_eq!(image.resized(832, 832), image.url);
+        let image = ProductImage::from("https://rukminim2.flixcart.com/www/promos/a.png");
+        assert_eq!(image.max_quality(), image.url);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_as_its_url() {
+        let image = ProductImage::from("https://rukminim2.flixcart.com/image/128/128/a.jpeg");
+        let json = serde_json::to_string(&image).unwrap();
+        assert_eq!(
+            json,
+            r#""https://rukminim2.flixcart.com/image/128/128/a.jpeg""#
+        );
+        assert_eq!(serde_json::from_str::<ProductImage>(&json).unwrap(), image);
+    }
+}
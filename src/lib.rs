@@ -3,15 +3,41 @@
 //! `ProductDetails` can fetch details from product's URL
 //! and `ProductSearch` can search a product from a
 //! given search query from Flipkart.
+//! `ProductReviews` can fetch the customer reviews of a product.
+//!
+//! `FlipkartClient` can be used to share a single HTTP client
+//! across many requests, and a custom `Fetcher` can be plugged
+//! into it to get the webpages from somewhere else.
 //!
 //! Feature Flags:
 //! - `serde`: Enables serde support for the structs. (default)
 
+mod client;
+mod error;
+mod fetcher;
+mod image;
+mod link;
+mod parse;
+mod price;
 pub mod product_details;
+mod product_ref;
+mod rate_limit;
+mod retry;
+pub mod reviews;
 pub mod search;
+pub use async_trait::async_trait;
+pub use client::{FlipkartClient, FlipkartClientBuilder};
+pub use error::{Error, ErrorKind, Result};
+pub use fetcher::{Fetcher, Page, ReqwestFetcher};
 use header::{HeaderMap, HeaderValue};
-pub use product_details::ProductDetails;
+pub use image::ProductImage;
+pub use price::{Currency, Price};
+pub use product_details::{ProductDetails, ProductSellers};
+pub use product_ref::ProductRef;
+pub use rate_limit::RateLimiter;
 use reqwest::header;
+pub use retry::RetryPolicy;
+pub use reviews::ProductReviews;
 pub use search::ProductSearch;
 pub use url::Url;
 
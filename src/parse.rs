@@ -0,0 +1,32 @@
+use scraper::Html;
+
+/// Parses a count like `12,345`.
+pub(crate) fn parse_count(text: &str) -> Option<u32> {
+    text.trim().replace(',', "").parse::<u32>().ok()
+}
+
+/// Parses the number of ratings and reviews from
+/// a text like `12,345 Ratings & 1,234 Reviews`.
+pub(crate) fn parse_rating_counts(text: &str) -> (Option<u32>, Option<u32>) {
+    let mut rating_count = None;
+    let mut review_count = None;
+    for count in text.split('&') {
+        let mut words = count.split_whitespace();
+        let number = words.next().and_then(parse_count);
+        match words.next() {
+            Some(word) if word.starts_with("Rating") => rating_count = number,
+            Some(word) if word.starts_with("Review") => review_count = number,
+            _ => {}
+        }
+    }
+    (rating_count, review_count)
+}
+
+/// Finds the total number of pages from the `Page 1 of 25`
+/// text shown at the bottom of paginated pages.
+pub(crate) fn total_pages(document: &Html) -> Option<u32> {
+    document.root_element().text().find_map(|text| {
+        let (_, total) = text.trim().strip_prefix("Page ")?.split_once(" of ")?;
+        parse_count(total)
+    })
+}
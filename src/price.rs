@@ -0,0 +1,208 @@
+use std::fmt;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+/// Currency of a price.
+pub enum Currency {
+    /// Indian Rupee.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "INR"))]
+    Inr,
+}
+
+impl Currency {
+    /// ISO 4217 code of the currency, like `INR`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Inr => "INR",
+        }
+    }
+
+    /// Symbol of the currency, like `₹`.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Currency::Inr => "₹",
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// A price, stored in paise so decimal prices like `₹49.50` are exact.
+///
+/// Displayed with Indian digit grouping, like `₹1,23,456`.
+///
+/// ```rust
+/// use flipkart_scraper::Price;
+///
+/// let original = Price::parse("₹1,29,999").unwrap();
+/// let current = Price::parse("₹99,999.50").unwrap();
+/// assert_eq!(current.paise, 99_99_950);
+/// assert_eq!(current.to_string(), "₹99,999.50");
+/// assert_eq!(original.discount_amount(&current).unwrap().to_string(), "₹29,999.50");
+/// assert_eq!(original.discount_percent(&current), Some(23));
+/// ```
+pub struct Price {
+    /// Amount in paise, the hundredth of a rupee.
+    pub paise: i64,
+    /// Currency of the amount.
+    pub currency: Currency,
+}
+
+impl Price {
+    /// Creates a price in rupees from an amount in paise.
+    pub fn from_paise(paise: i64) -> Self {
+        Price {
+            paise,
+            currency: Currency::Inr,
+        }
+    }
+
+    /// Creates a price in rupees from a whole amount of rupees.
+    pub fn from_rupees(rupees: i64) -> Self {
+        Self::from_paise(rupees * 100)
+    }
+
+    /// Creates a price in rupees from a decimal amount,
+    /// rounded to the nearest paisa.
+    pub(crate) fn from_decimal(rupees: f64) -> Self {
+        Self::from_paise((rupees * 100.0).round() as i64)
+    }
+
+    /// Parses a price like `₹1,23,456` or `₹49.50`, the `₹` being optional.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = text
+            .strip_prefix('₹')
+            .unwrap_or(text)
+            .trim()
+            .replace(',', "");
+        let (rupees, paise) = text.split_once('.').unwrap_or((&text, ""));
+        if rupees.is_empty() || !rupees.bytes().all(|digit| digit.is_ascii_digit()) {
+            return None;
+        }
+        if paise.len() > 2 || !paise.bytes().all(|digit| digit.is_ascii_digit()) {
+            return None;
+        }
+        let rupees = rupees.parse::<i64>().ok()?;
+        // `.5` is fifty paise
+        let paise = format!("{paise:0<2}").parse::<i64>().ok()?;
+        Some(Self::from_paise(
+            rupees.checked_mul(100)?.checked_add(paise)?,
+        ))
+    }
+
+    /// Whole rupees of the price, leaving out the paise.
+    pub fn rupees(&self) -> i64 {
+        self.paise / 100
+    }
+
+    /// Amount saved when this original price is reduced to the current price,
+    /// negative if the current price is higher.
+    ///
+    /// Returns `None` if the prices are in different currencies
+    /// or the amount overflows.
+    pub fn discount_amount(&self, current: &Price) -> Option<Price> {
+        if self.currency != current.currency {
+            return None;
+        }
+        Some(Price {
+            paise: self.paise.checked_sub(current.paise)?,
+            currency: self.currency,
+        })
+    }
+
+    /// Percentage saved when this original price is reduced to the current price,
+    /// rounded down like Flipkart's `23% off`.
+    ///
+    /// Returns `None` if the prices are in different currencies,
+    /// the original price is not positive or the current price is higher.
+    pub fn discount_percent(&self, current: &Price) -> Option<u8> {
+        let discount = self.discount_amount(current)?;
+        if self.paise <= 0 || discount.paise < 0 {
+            return None;
+        }
+        // widened so that the multiplication can't overflow
+        let percent = i128::from(discount.paise) * 100 / i128::from(self.paise);
+        u8::try_from(percent).ok()
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.paise < 0 {
+            f.write_str("-")?;
+        }
+        f.write_str(self.currency.symbol())?;
+
+        let paise = self.paise.unsigned_abs();
+        let rupees = (paise / 100).to_string();
+        // last three digits are grouped together, the rest in pairs
+        let (rest, last) = rupees.split_at(rupees.len().saturating_sub(3));
+        let start = rest.len() % 2;
+        if start == 1 {
+            write!(f, "{},", &rest[..1])?;
+        }
+        for pair in rest.as_bytes()[start..].chunks(2) {
+            write!(f, "{}{},", pair[0] as char, pair[1] as char)?;
+        }
+        f.write_str(last)?;
+
+        let fraction = paise % 100;
+        if fraction != 0 {
+            write!(f, ".{fraction:02}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_digits_the_indian_way() {
+        let display = |rupees| Price::from_rupees(rupees).to_string();
+        assert_eq!(display(0), "₹0");
+        assert_eq!(display(999), "₹999");
+        assert_eq!(display(1000), "₹1,000");
+        assert_eq!(display(12345), "₹12,345");
+        assert_eq!(display(123456), "₹1,23,456");
+        assert_eq!(display(1234567), "₹12,34,567");
+        assert_eq!(display(12345678), "₹1,23,45,678");
+    }
+
+    #[test]
+    fn shows_paise_when_there_are_some() {
+        assert_eq!(Price::from_paise(5).to_string(), "₹0.05");
+        assert_eq!(Price::from_paise(123456750).to_string(), "₹12,34,567.50");
+    }
+
+    #[test]
+    fn puts_the_sign_before_the_symbol() {
+        assert_eq!(Price::from_paise(-50).to_string(), "-₹0.50");
+        assert_eq!(Price::from_rupees(-123456).to_string(), "-₹1,23,456");
+        assert_eq!(
+            Price::from_paise(i64::MIN).to_string(),
+            "-₹92,23,37,20,36,85,47,758.08"
+        );
+    }
+
+    #[test]
+    fn discounts_do_not_overflow() {
+        let original = Price::from_paise(i64::MAX);
+        assert_eq!(original.discount_percent(&Price::from_paise(0)), Some(100));
+        assert_eq!(
+            original.discount_percent(&Price::from_paise(i64::MAX / 2)),
+            Some(50)
+        );
+        assert_eq!(original.discount_amount(&Price::from_paise(-1)), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serializes_the_currency_code() {
+        assert_eq!(serde_json::to_string(&Currency::Inr).unwrap(), r#""INR""#);
+    }
+}
@@ -0,0 +1,108 @@
+use scraper::{ElementRef, Html, Selector};
+use serde_json::Value;
+use url::Url;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// A level of the category path of a Product,
+/// like `Electronics` or `Mobiles`.
+pub struct Breadcrumb {
+    /// Name of the category.
+    pub name: String,
+    /// Link to the category page, if the level has one.
+    pub url: Option<String>,
+}
+
+impl Breadcrumb {
+    /// Parses the category path of a product page, starting from `Home`.
+    ///
+    /// Reads the schema.org `BreadcrumbList` if the page has one,
+    /// otherwise the links of the breadcrumb trail of the page.
+    pub(crate) fn parse(document: &Html, schemas: &[Value], url: &Url) -> Vec<Self> {
+        let breadcrumbs = Self::parse_schema(schemas, url);
+        if !breadcrumbs.is_empty() {
+            return breadcrumbs;
+        }
+        Self::parse_trail(document, url)
+    }
+
+    fn parse_schema(schemas: &[Value], url: &Url) -> Vec<Self> {
+        let list = schemas
+            .iter()
+            .flat_map(
+                |schema| match schema.get("@graph").and_then(Value::as_array) {
+                    Some(graph) => graph.iter().collect(),
+                    None => vec![schema],
+                },
+            )
+            .find(|schema| schema.get("@type").and_then(Value::as_str) == Some("BreadcrumbList"));
+        let Some(items) = list
+            .and_then(|list| list.get("itemListElement"))
+            .and_then(Value::as_array)
+        else {
+            return Vec::new();
+        };
+
+        let mut items: Vec<_> = items.iter().collect();
+        items.sort_by_key(|item| item.get("position").and_then(Value::as_u64));
+        items
+            .into_iter()
+            .filter_map(|item| {
+                // the name and link are either on the list item or its `item`
+                let inner = item.get("item");
+                let name = item
+                    .get("name")
+                    .or_else(|| inner?.get("name"))
+                    .and_then(Value::as_str)?;
+                let link = inner
+                    .and_then(|inner| inner.as_str().or_else(|| inner.get("@id")?.as_str()))
+                    .and_then(|link| url.join(link).ok())
+                    .map(String::from);
+                Some(Breadcrumb {
+                    name: name.trim().to_string(),
+                    url: link,
+                })
+            })
+            .collect()
+    }
+
+    fn parse_trail(document: &Html, url: &Url) -> Vec<Self> {
+        let link_selector = &Selector::parse("a").unwrap();
+
+        // trail starts with a `Home` link to the home page
+        let Some(home) = document.select(link_selector).find(|link| {
+            link.text().collect::<String>().trim() == "Home"
+                && link
+                    .value()
+                    .attr("href")
+                    .and_then(|href| url.join(href).ok())
+                    .is_some_and(|href| href.path() == "/")
+        }) else {
+            return Vec::new();
+        };
+        // trail is the closest container with more links than the home link
+        let Some(trail) = home
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .find(|ancestor| ancestor.select(link_selector).nth(1).is_some())
+        else {
+            return Vec::new();
+        };
+
+        trail
+            .select(link_selector)
+            .filter_map(|link| {
+                let name = link.text().collect::<String>().trim().to_string();
+                if name.is_empty() {
+                    return None;
+                }
+                let link = link
+                    .value()
+                    .attr("href")
+                    .and_then(|href| url.join(href).ok())
+                    .map(String::from);
+                Some(Breadcrumb { name, url: link })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,99 @@
+use crate::Price;
+use scraper::{ElementRef, Html, Selector};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// Delivery of a Product to a pincode.
+pub struct Delivery {
+    /// Pincode the page shows the delivery details are for,
+    /// `None` if it doesn't show one.
+    pub pincode: Option<String>,
+    /// Whether the product can be delivered to the pincode.
+    pub serviceable: bool,
+    /// Delivery estimate, like `Delivery by 12 Oct, Thursday`.
+    pub estimate: Option<String>,
+    /// Delivery charge, zero for free delivery.
+    pub charge: Option<Price>,
+    /// Whether cash on delivery is available, if shown.
+    pub cash_on_delivery: Option<bool>,
+}
+
+impl Delivery {
+    /// Parses the delivery section of a product page,
+    /// returns `None` if the page doesn't have one.
+    pub(crate) fn parse(document: &Html) -> Option<Self> {
+        let div_selector = &Selector::parse("div").unwrap();
+        let pincode_selector = &Selector::parse(PINCODE_INPUT).unwrap();
+
+        let mut delivery = Delivery {
+            pincode: shown_pincode(document),
+            ..Default::default()
+        };
+
+        // estimate is shown like `Delivery by 12 Oct, Thursday | Free ₹40`
+        // where the striked out charge is waived
+        let estimate = document.select(div_selector).find(|div| {
+            div.first_child()
+                .and_then(|text| text.value().as_text())
+                .is_some_and(|text| {
+                    let text = text.trim_start();
+                    text.starts_with("Delivery by") || text.starts_with("Delivered by")
+                })
+        });
+        if let Some(text) = estimate.map(|div| div.text().collect::<String>()) {
+            let (estimate, charge) = text.split_once('|').unwrap_or((&text, ""));
+            delivery.estimate = Some(estimate.trim().to_string());
+            delivery.charge = if charge.contains("Free") {
+                Some(Price::default())
+            } else {
+                Price::parse(charge)
+            };
+        }
+
+        // messages are only read from the delivery section, which is the
+        // closest container of the pincode input (or the estimate) that
+        // mentions delivery, so scripts and other products are left out
+        let section = document
+            .select(pincode_selector)
+            .next()
+            .or(estimate)
+            .and_then(|anchor| {
+                anchor
+                    .ancestors()
+                    .filter_map(ElementRef::wrap)
+                    .find(|ancestor| ancestor.text().any(|text| text.contains("eliver")))
+            })
+            .map(|section| section.text().collect::<String>())
+            .unwrap_or_default();
+
+        if section.contains("Cash on Delivery available") {
+            delivery.cash_on_delivery = Some(true);
+        } else if section.contains("Cash on Delivery not available")
+            || section.contains("Cash on Delivery is not available")
+        {
+            delivery.cash_on_delivery = Some(false);
+        }
+
+        let unserviceable = ["Not deliverable", "not serviceable", "does not deliver"]
+            .iter()
+            .any(|text| section.contains(text));
+        if delivery.estimate.is_none() && !unserviceable {
+            return None;
+        }
+        delivery.serviceable = !unserviceable;
+        Some(delivery)
+    }
+}
+
+/// Selector of the input showing the pincode the page is for.
+const PINCODE_INPUT: &str = "input#pincodeInputId";
+
+/// Finds the pincode a product or sellers page shows it is for.
+pub(crate) fn shown_pincode(document: &Html) -> Option<String> {
+    let pincode_selector = &Selector::parse(PINCODE_INPUT).unwrap();
+    document
+        .select(pincode_selector)
+        .find_map(|input| input.value().attr("value"))
+        .map(|pincode| pincode.trim().to_string())
+        .filter(|pincode| !pincode.is_empty())
+}
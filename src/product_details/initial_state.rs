@@ -0,0 +1,338 @@
+use crate::product_details::{
+    ProductDetails, RatingSummary, Seller, Specification, Specifications, StarCount,
+};
+use crate::{Price, ProductImage};
+use scraper::{Html, Selector};
+use serde_json::Value;
+use std::collections::VecDeque;
+use url::Url;
+
+#[derive(Default, Debug)]
+/// Details of a product from the `window.__INITIAL_STATE__` JSON the
+/// product page is hydrated from.
+///
+/// The JSON doesn't depend on the obfuscated CSS classes of the page,
+/// so its values are preferred over the ones scraped from the page.
+pub(crate) struct InitialState {
+    product_id: Option<String>,
+    brand: Option<String>,
+    share_url: Option<String>,
+    current_price: Option<Price>,
+    original_price: Option<Price>,
+    rating: Option<f32>,
+    rating_count: Option<u32>,
+    review_count: Option<u32>,
+    stars: Vec<StarCount>,
+    seller: Option<Seller>,
+    images: Vec<String>,
+    specifications: Vec<Specifications>,
+    in_stock: Option<bool>,
+}
+
+impl InitialState {
+    /// Finds and parses the `window.__INITIAL_STATE__` script of a product page.
+    ///
+    /// The JSON also holds recommendations and other products, so only the
+    /// part describing the product with the given pid, or the page's own
+    /// product, is read.
+    ///
+    /// Returns `None` if the page has no such script or its JSON is invalid,
+    /// and the default (empty) state if the JSON has no main product.
+    pub(crate) fn parse(document: &Html, pid: Option<&str>) -> Option<Self> {
+        let script_selector = &Selector::parse("script").unwrap();
+        let script = document
+            .select(script_selector)
+            .map(|script| script.text().collect::<String>())
+            .find(|text| text.trim_start().starts_with("window.__INITIAL_STATE__"))?;
+        let (_, json) = script.split_once('=')?;
+        // the script may continue after the object, only read the object
+        let state = serde_json::Deserializer::from_str(json)
+            .into_iter::<Value>()
+            .next()?
+            .ok()?;
+        Some(Self::from_value(&state, pid))
+    }
+
+    fn from_value(state: &Value, pid: Option<&str>) -> Self {
+        let Some(product) = Product::locate(state, pid) else {
+            return Self::default();
+        };
+        let product_id = product.id;
+        let mut details = InitialState {
+            product_id: product_id.map(String::from),
+            brand: product
+                .find("brand")
+                .and_then(Value::as_str)
+                .map(String::from),
+            share_url: product
+                .strings()
+                .find(|text| is_share_url(text, None))
+                .or_else(|| {
+                    // share URL may be kept outside the product, so
+                    // only take one which links to the product
+                    let id = product_id?;
+                    Product::all(state)
+                        .strings()
+                        .find(|text| is_share_url(text, Some(id)))
+                })
+                .map(String::from),
+            current_price: product
+                .find("finalPrice")
+                .or_else(|| product.find("sellingPrice"))
+                .and_then(price),
+            original_price: product.find("mrp").and_then(price),
+            ..Default::default()
+        };
+
+        if let Some(rating) = product
+            .find_all("rating")
+            .find(|rating| rating.get("average").is_some())
+        {
+            details.rating = rating
+                .get("average")
+                .and_then(Value::as_f64)
+                .map(|average| average as f32);
+            details.rating_count = rating.get("count").and_then(count);
+            details.review_count = rating.get("reviewCount").and_then(count);
+            // breakup is the number of ratings from 1 star up to 5 stars
+            if let Some(breakup) = rating.get("breakup").and_then(Value::as_array) {
+                if let Some(counts) = breakup.iter().map(count).collect::<Option<Vec<_>>>() {
+                    if counts.len() == 5 {
+                        details.stars = (1..=5)
+                            .zip(counts)
+                            .rev()
+                            .map(|(stars, count)| StarCount { stars, count })
+                            .collect();
+                    }
+                }
+            }
+        }
+
+        details.seller = product.find("sellerInfo").and_then(|seller| {
+            let seller = Product::all(seller);
+            let name = seller
+                .find("name")
+                .or_else(|| seller.find("sellerName"))
+                .and_then(Value::as_str)?;
+            let rating = seller.find("rating").and_then(|rating| {
+                rating
+                    .as_f64()
+                    .or_else(|| rating.get("average")?.as_f64())
+                    .map(|rating| rating as f32)
+            });
+            Some(Seller {
+                name: name.to_string(),
+                rating,
+            })
+        });
+
+        // images are templates like `https://rukminim1.flixcart.com/image/{@width}/{@height}/...?q={@quality}`
+        for image in product.strings().filter(|text| text.contains("{@width}")) {
+            let image = image
+                .replace("{@width}", "128")
+                .replace("{@height}", "128")
+                .replace("{@quality}", "70");
+            if !details.images.contains(&image) {
+                details.images.push(image);
+            }
+        }
+
+        if let Some(groups) = product.find("specifications").and_then(Value::as_array) {
+            details.specifications = groups
+                .iter()
+                .filter_map(|group| {
+                    let category = group
+                        .get("name")
+                        .or_else(|| group.get("title"))
+                        .and_then(Value::as_str)?;
+                    let specifications = group
+                        .get("attributes")
+                        .and_then(Value::as_array)?
+                        .iter()
+                        .filter_map(|attribute| {
+                            let name = attribute.get("name").and_then(Value::as_str)?;
+                            let value = match attribute.get("values").or(attribute.get("value"))? {
+                                Value::Array(values) => values
+                                    .iter()
+                                    .filter_map(Value::as_str)
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                                Value::String(value) => value.to_owned(),
+                                _ => return None,
+                            };
+                            Some(Specification {
+                                name: name.to_string(),
+                                value,
+                            })
+                        })
+                        .collect();
+                    Some(Specifications {
+                        category: category.to_string(),
+                        specifications,
+                    })
+                })
+                .collect();
+        }
+
+        details.in_stock = product
+            .find("availabilityStatus")
+            .or_else(|| product.find("displayState"))
+            .and_then(Value::as_str)
+            .and_then(|status| match status {
+                _ if status.contains("OUT_OF_STOCK") || status.contains("COMING_SOON") => {
+                    Some(false)
+                }
+                _ if status.contains("IN_STOCK") => Some(true),
+                _ => None,
+            });
+
+        details
+    }
+
+    /// Fills the details with the values from the JSON,
+    /// keeping the scraped values for the ones missing in it.
+    pub(crate) fn fill(self, details: &mut ProductDetails) {
+        details.product_id = self.product_id.or(details.product_id.take());
+        details.brand = self.brand.or(details.brand.take());
+        if let Some(share_url) = self.share_url {
+            details.share_url = share_url;
+        }
+        if let Some(in_stock) = self.in_stock {
+            details.in_stock = in_stock;
+        }
+        if self.current_price.is_some() {
+            details.current_price = self.current_price;
+            details.original_price = self.original_price.or(self.current_price);
+        }
+        details.rating = self.rating.or(details.rating);
+        details.seller = self.seller.or(details.seller.take());
+        if !self.images.is_empty() {
+            details.thumbnails = self.images.into_iter().map(ProductImage::from).collect();
+        }
+        if !self.specifications.is_empty() {
+            details.specifications = self.specifications;
+        }
+
+        if self.rating_count.is_some() || self.review_count.is_some() || !self.stars.is_empty() {
+            let summary = details
+                .rating_summary
+                .get_or_insert_with(RatingSummary::default);
+            summary.rating_count = self.rating_count.or(summary.rating_count);
+            summary.review_count = self.review_count.or(summary.review_count);
+            if !self.stars.is_empty() {
+                summary.stars = self.stars;
+            }
+        }
+    }
+}
+
+/// Part of the JSON describing a product, leaving out
+/// the other products nested in it, like recommendations.
+#[derive(Clone, Copy)]
+struct Product<'a> {
+    root: &'a Value,
+    /// Product ID of the product, objects with another one are left out.
+    id: Option<&'a str>,
+}
+
+impl<'a> Product<'a> {
+    /// Finds the product with the given pid, as the object with the most
+    /// data identifying itself with that pid, or else the page's own
+    /// product, which is described by its `pageContext`.
+    fn locate(state: &'a Value, pid: Option<&str>) -> Option<Self> {
+        let all = Product::all(state);
+        let root = pid
+            .and_then(|pid| {
+                all.values()
+                    .filter(|value| product_id(value) == Some(pid))
+                    .max_by_key(|value| Product::all(value).values().count())
+            })
+            .or_else(|| {
+                all.find_all("pageContext")
+                    .find(|context| product_id(context).is_some())
+            })?;
+        Some(Product {
+            root,
+            id: product_id(root),
+        })
+    }
+
+    /// The whole value, without leaving anything out.
+    fn all(root: &'a Value) -> Self {
+        Product { root, id: None }
+    }
+
+    /// Iterates over the values of the product, breadth first
+    /// so values of the product come before nested ones.
+    fn values(self) -> impl Iterator<Item = &'a Value> {
+        let mut queue = VecDeque::from([self.root]);
+        let is_own = move |value: &&Value| match (product_id(value), self.id) {
+            (Some(other), Some(id)) => other == id,
+            _ => true,
+        };
+        std::iter::from_fn(move || {
+            let value = queue.pop_front()?;
+            match value {
+                Value::Object(map) => queue.extend(map.values().filter(is_own)),
+                Value::Array(values) => queue.extend(values.iter().filter(is_own)),
+                _ => {}
+            }
+            Some(value)
+        })
+    }
+
+    /// Finds the value of the key closest to the root of the product.
+    fn find(self, key: &str) -> Option<&'a Value> {
+        self.values().find_map(|value| value.get(key))
+    }
+
+    /// Finds all the values of the key, closest to the root first.
+    fn find_all(self, key: &'a str) -> impl Iterator<Item = &'a Value> {
+        self.values().filter_map(move |value| value.get(key))
+    }
+
+    /// Iterates over all the strings of the product.
+    fn strings(self) -> impl Iterator<Item = &'a str> {
+        self.values().filter_map(Value::as_str)
+    }
+}
+
+/// Product ID an object identifies itself with.
+fn product_id(value: &Value) -> Option<&str> {
+    value
+        .get("productId")
+        .or_else(|| value.get("pid"))?
+        .as_str()
+}
+
+/// Checks whether the text is a share URL, of the product
+/// with the given pid if one is given.
+fn is_share_url(text: &str, pid: Option<&str>) -> bool {
+    if !text.contains("product.share.pp") {
+        return false;
+    }
+    let Ok(url) = Url::parse(text) else {
+        return false;
+    };
+    pid.map_or(true, |pid| {
+        url.query_pairs()
+            .any(|(key, value)| key == "pid" && value == pid)
+    })
+}
+
+/// Reads a price given either as a number or as `{ "value": number }`.
+fn price(value: &Value) -> Option<Price> {
+    value
+        .as_f64()
+        .or_else(|| value.get("value")?.as_f64())
+        .map(Price::from_decimal)
+}
+
+/// Reads a count given either as a number or as a string like `12,345`.
+fn count(value: &Value) -> Option<u32> {
+    match value {
+        Value::Number(count) => count.as_u64().and_then(|count| count.try_into().ok()),
+        Value::String(count) => crate::parse::parse_count(count),
+        _ => None,
+    }
+}
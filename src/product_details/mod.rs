@@ -2,7 +2,22 @@ mod product;
 pub use product::*;
 mod seller;
 pub use seller::*;
+mod sellers;
+pub use sellers::*;
 mod offer;
 pub use offer::*;
 mod specs;
 pub use specs::*;
+mod rating;
+pub use rating::*;
+mod variant;
+pub use variant::*;
+mod delivery;
+pub use delivery::Delivery;
+pub(crate) use delivery::shown_pincode;
+mod breadcrumb;
+pub use breadcrumb::*;
+mod structured_data;
+pub use structured_data::*;
+mod initial_state;
+pub(crate) use initial_state::InitialState;
@@ -1,6 +1,8 @@
-use crate::product_details::{Offer, Seller, Specification, Specifications};
-use eyre::{bail, eyre, Result};
-use reqwest::Client;
+use crate::product_details::{
+    Breadcrumb, Delivery, InitialState, Offer, RatingSummary, Seller, Specification,
+    Specifications, StructuredData, VariantGroup,
+};
+use crate::{Error, FlipkartClient, Price, ProductImage, ProductRef, Result};
 use scraper::{Html, Selector};
 use url::Url;
 
@@ -9,40 +11,74 @@ use url::Url;
 /// The details of a Flipkart Product.
 ///
 /// Use the `ProductDetails::fetch` method to fetch the details of a product
-/// from the product url.
+/// from the product url, or `ProductDetails::from_html` to parse an
+/// already fetched product page.
 pub struct ProductDetails {
     /// Product name
     pub name: Option<String>,
+    /// Brand of the product.
+    pub brand: Option<String>,
+    /// Category path of the product, like
+    /// `Home > Electronics > Mobiles > Samsung Mobiles`.
+    pub breadcrumbs: Vec<Breadcrumb>,
     /// Whether the product is in stock or not.
     pub in_stock: bool,
+    /// Delivery estimate, charge and serviceability for the pincode
+    /// shown on the page.
+    ///
+    /// When fetched with a client which has a pincode set, it is `None`
+    /// unless the page shows the details are for that pincode.
+    pub delivery: Option<Delivery>,
     /// Current price of the product.
-    pub current_price: Option<i32>,
+    pub current_price: Option<Price>,
     /// Original price of the product.
-    pub original_price: Option<i32>,
+    pub original_price: Option<Price>,
     /// Product ID
     pub product_id: Option<String>,
+    /// Identifiers of the product parsed from its URL.
+    pub product_ref: Option<ProductRef>,
     /// URL to product, usually shortened and cleaner.
     pub share_url: String,
+    /// URL of the product page, after following redirects.
+    pub final_url: String,
+    /// URLs which redirected to the product page, starting with the requested one.
+    /// Empty if the request was not redirected.
+    pub redirect_chain: Vec<String>,
+    /// Whether the requested product was redirected to a different
+    /// product or variant, identified by its product ID or item ID.
+    pub moved: bool,
     /// Rating of the product.
     pub rating: Option<f32>,
+    /// Number of ratings for each star, number of reviews
+    /// and ratings of particular aspects of the product.
+    pub rating_summary: Option<RatingSummary>,
     /// Whether it is f-assured produtc or not.
     pub f_assured: bool,
     /// Highlights of the product.
     pub highlights: Vec<String>,
     /// Primary seller of the product.
     pub seller: Option<Seller>,
-    /// URL to thumbnails of the product.
-    pub thumbnails: Vec<String>,
+    /// Images of the product, in the order of its gallery.
+    pub thumbnails: Vec<ProductImage>,
     /// Offers available on the product.
     pub offers: Vec<Offer>,
     /// Specifications of the product.
     pub specifications: Vec<Specifications>,
+    /// Variants of the product, grouped by the dimension they differ in.
+    pub variants: Vec<VariantGroup>,
+    /// schema.org data embedded in the page (brand, GTIN, SKU,
+    /// aggregate rating and offer), `None` if the page has none.
+    pub structured_data: Option<StructuredData>,
 }
 
 impl ProductDetails {
     /// Fetches a product from the given url.
     ///
-    /// ```rust
+    /// Mobile (`m.flipkart.com`), deep (`dl.flipkart.com`) and short
+    /// (`fkrt.it`) links are accepted and resolved to the product's
+    /// `www.flipkart.com` page, any other host is rejected.
+    ///
+    /// ```rust,no_run
     /// use std::error::Error;
     /// use flipkart_scraper::{ProductDetails, Url};
     ///
@@ -53,8 +89,95 @@ impl ProductDetails {
     ///     println!("{:#?}", details);
     ///     Ok(())
     /// }
-    // ```
+    /// ```
+    ///
+    /// Builds a new client for every call, use `FlipkartClient::product`
+    /// when making multiple requests.
     pub async fn fetch(url: Url) -> Result<Self> {
+        FlipkartClient::new()?.product(url).await
+    }
+
+    /// Fetches the product with the given product ID.
+    ///
+    /// ```rust,no_run
+    /// use std::error::Error;
+    /// use flipkart_scraper::ProductDetails;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let details = ProductDetails::fetch_by_pid("MOBGENJWBPFYJSFT").await;
+    ///     println!("{:#?}", details);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Builds a new client for every call, use `FlipkartClient::product_by_pid`
+    /// when making multiple requests.
+    pub async fn fetch_by_pid(pid: impl Into<String>) -> Result<Self> {
+        FlipkartClient::new()?.product_by_pid(pid).await
+    }
+
+    /// Fetches the referenced product.
+    ///
+    /// Builds a new client for every call, use `FlipkartClient::product_by_ref`
+    /// when making multiple requests.
+    pub async fn fetch_by_ref(product: &ProductRef) -> Result<Self> {
+        FlipkartClient::new()?.product_by_ref(product).await
+    }
+
+    /// Fetches a product from the given url using the given client.
+    pub(crate) async fn fetch_with(client: &FlipkartClient, url: Url) -> Result<Self> {
+        // parsing is retried along with the request, as the error page
+        // is sometimes served with a success status
+        let (redirects, mut details) = client
+            .retry(|| async {
+                let page = client.fetch_link(&url).await?;
+                let details = Self::from_html(page.url, &page.body)?;
+                Ok((page.redirects, details))
+            })
+            .await?;
+        details.redirect_chain = redirects.into_iter().map(String::from).collect();
+        if let Some(requested) = ProductRef::from_url(&url) {
+            details.moved = details.is_moved_from(&requested);
+        }
+        // delivery details are for another location unless the page shows the pincode
+        if let Some(pincode) = client.pincode() {
+            let shown = details
+                .delivery
+                .as_ref()
+                .and_then(|delivery| delivery.pincode.as_deref());
+            if shown != Some(pincode) {
+                details.delivery = None;
+            }
+        }
+        Ok(details)
+    }
+
+    /// Checks whether the resolved product differs from the requested one.
+    fn is_moved_from(&self, requested: &ProductRef) -> bool {
+        let resolved = self.product_ref.as_ref();
+        if let Some(requested_pid) = &requested.pid {
+            let resolved_pid = self
+                .product_id
+                .as_ref()
+                .or(resolved.and_then(|product| product.pid.as_ref()));
+            return resolved_pid.is_some_and(|pid| pid != requested_pid);
+        }
+        let resolved_item_id = resolved.and_then(|product| product.item_id.as_ref());
+        match (&requested.item_id, resolved_item_id) {
+            (Some(requested_id), Some(resolved_id)) => requested_id != resolved_id,
+            _ => false,
+        }
+    }
+
+    /// Parses the details of a product from the body of its already fetched webpage.
+    ///
+    /// `url` is the link to the product page, it is used as the `share_url`
+    /// when the page doesn't provide one.
+    ///
+    /// Returns `Error::Parse` if the page has neither a title
+    /// nor the `window.__INITIAL_STATE__` product data.
+    pub fn from_html(url: Url, body: &str) -> Result<Self> {
         let div_selector = &Selector::parse("div").unwrap();
         let h1_selector = &Selector::parse("h1").unwrap();
         let title_selector = &Selector::parse("title").unwrap();
@@ -68,27 +191,13 @@ impl ProductDetails {
         let tr_selector = &Selector::parse("tr").unwrap();
         let td_selector = &Selector::parse("td").unwrap();
 
-        if !url
-            .domain()
-            .ok_or_else(|| eyre!("Domain name invalid."))?
-            .contains("flipkart.com")
-        {
-            bail!("Only flipkart.com is supported");
-        }
-
-        let client = Client::builder()
-            .default_headers(crate::build_headers())
-            .build()?;
-
-        let webpage = client.get(url.to_owned()).send().await?;
-        let body = webpage.text().await?;
         if body.contains("has been moved or deleted") || body.contains("not right!") {
-            bail!("Link provided doesn't corresponds to any product");
+            return Err(Error::ProductNotFound);
         }
         if body.contains("Internal Server Error") {
-            bail!("Internal Server Error. Host is down or is blocking use of this library.");
+            return Err(Error::Blocked);
         }
-        let document = Html::parse_document(&body);
+        let document = Html::parse_document(body);
 
         let mut details = ProductDetails::default();
 
@@ -97,6 +206,19 @@ impl ProductDetails {
             .next()
             .or(document.select(title_selector).next())
             .map(|title| title.text().collect::<String>());
+        // a product page always has a title or the product data
+        let has_state = document.select(script_selector).any(|script| {
+            script
+                .text()
+                .collect::<String>()
+                .trim_start()
+                .starts_with("window.__INITIAL_STATE__")
+        });
+        if title.is_none() && !has_state {
+            return Err(Error::Parse(
+                "page has neither a title nor product data".into(),
+            ));
+        }
         details.name = title;
 
         // thumbnails
@@ -122,6 +244,8 @@ impl ProductDetails {
         let in_stock = !(coming_soon || body.contains("currently out of stock"));
         details.in_stock = in_stock;
 
+        details.delivery = Delivery::parse(&document);
+
         if in_stock {
             let seller = document
                 .select(seller_selector)
@@ -261,7 +385,7 @@ impl ProductDetails {
                     if text.contains('₹') {
                         continue;
                     }
-                    let price_tag = text.replace(',', "").parse::<i32>().ok();
+                    let price_tag = Price::parse(text);
                     if details.current_price.is_none() {
                         details.current_price = price_tag;
                     } else {
@@ -272,25 +396,79 @@ impl ProductDetails {
             }
         }
 
-        'link_identifier: for element in document.select(script_selector) {
-            let text = element.text().collect::<String>();
-            if text.starts_with("window.__INITIAL_STATE__") {
-                if let Some((_, id_container)) = text.split_once("productId") {
-                    let pattern: &[_] = &['"', ':'];
-                    let id_container = id_container.trim().trim_matches(pattern);
-                    details.product_id = id_container.split_once('"').map(|(id, _)| id.into());
-                }
-                for content in text.split_inclusive("product.share.pp") {
-                    if let Some(link_to_product) = content.rsplit_once('"') {
-                        // try parse url
-                        if let Ok(link) = Url::parse(link_to_product.1) {
-                            details.share_url = link.into();
-                            break 'link_identifier;
+        if !coming_soon {
+            details.rating_summary = RatingSummary::parse(&document);
+        }
+
+        // prefer the hydration JSON and fall back to looking for
+        // the identifiers in its text if it could not be parsed, the
+        // fallback is skipped on purpose when the JSON parsed without a
+        // main product, as the first identifiers in its text would be
+        // those of a recommended product
+        let pid = ProductRef::from_url(&url).and_then(|product| product.pid);
+        if let Some(state) = InitialState::parse(&document, pid.as_deref()) {
+            state.fill(&mut details);
+        } else {
+            'link_identifier: for element in document.select(script_selector) {
+                let text = element.text().collect::<String>();
+                if text.starts_with("window.__INITIAL_STATE__") {
+                    if let Some((_, id_container)) = text.split_once("productId") {
+                        let pattern: &[_] = &['"', ':'];
+                        let id_container = id_container.trim().trim_matches(pattern);
+                        details.product_id = id_container.split_once('"').map(|(id, _)| id.into());
+                    }
+                    for content in text.split_inclusive("product.share.pp") {
+                        if let Some(link_to_product) = content.rsplit_once('"') {
+                            // try parse url
+                            if let Ok(link) = Url::parse(link_to_product.1) {
+                                details.share_url = link.into();
+                                break 'link_identifier;
+                            }
                         }
                     }
                 }
             }
         }
+        details.structured_data = StructuredData::parse(&document);
+
+        let schemas = details
+            .structured_data
+            .as_ref()
+            .map(|data| data.schemas.as_slice())
+            .unwrap_or_default();
+        details.breadcrumbs = Breadcrumb::parse(&document, schemas, &url);
+        // trail usually ends with the product itself
+        let name = details.name.as_deref().map(str::trim);
+        if details.breadcrumbs.last().map(|level| level.name.as_str()) == name {
+            details.breadcrumbs.pop();
+        }
+
+        details.brand = details
+            .structured_data
+            .as_ref()
+            .and_then(|data| data.brand.clone())
+            .or(details.brand.take())
+            .or_else(|| {
+                details
+                    .specifications
+                    .iter()
+                    .flat_map(|group| &group.specifications)
+                    .find(|specification| specification.name == "Brand")
+                    .map(|specification| specification.value.clone())
+            });
+
+        details.product_ref = ProductRef::from_url(&url).map(|mut product_ref| {
+            if product_ref.pid.is_none() {
+                product_ref.pid = details.product_id.clone();
+            }
+            product_ref
+        });
+        let pid = details
+            .product_id
+            .as_deref()
+            .or(details.product_ref.as_ref().and_then(|r| r.pid.as_deref()));
+        details.variants = VariantGroup::parse(&document, pid);
+        details.final_url = url.to_string();
         if details.share_url.is_empty() {
             details.share_url = url.into();
         }
@@ -0,0 +1,137 @@
+use crate::parse::{parse_count, parse_rating_counts};
+use scraper::{ElementRef, Html, Selector};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// Summary of the customer ratings of a Product.
+pub struct RatingSummary {
+    /// Total number of ratings.
+    pub rating_count: Option<u32>,
+    /// Total number of reviews.
+    pub review_count: Option<u32>,
+    /// Number of ratings given with each number of stars,
+    /// from 5 stars down to 1 star.
+    pub stars: Vec<StarCount>,
+    /// Ratings of particular aspects of the product,
+    /// like `Camera`, `Battery` or `Display`.
+    pub aspects: Vec<AspectRating>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// Number of ratings given with a number of stars.
+pub struct StarCount {
+    /// Number of stars, from 1 to 5.
+    pub stars: u8,
+    /// Number of ratings given with that many stars.
+    pub count: u32,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// Rating of a particular aspect of a Product.
+pub struct AspectRating {
+    /// Name of the aspect, like `Camera`.
+    pub name: String,
+    /// Rating of the aspect, out of 5.
+    pub rating: f32,
+}
+
+impl RatingSummary {
+    /// Parses the ratings block of a product page,
+    /// returns `None` if the page doesn't have one.
+    pub(crate) fn parse(document: &Html) -> Option<Self> {
+        let span_selector = &Selector::parse("span").unwrap();
+        let ul_selector = &Selector::parse("ul").unwrap();
+        let li_selector = &Selector::parse("li").unwrap();
+        let rating_selector = &Selector::parse("div, text").unwrap();
+        let img_selector = &Selector::parse("img").unwrap();
+
+        let mut summary = RatingSummary::default();
+
+        if let Some(text) = document
+            .select(span_selector)
+            .map(|span| span.text().collect::<String>())
+            .find(|text| text.contains("Ratings") && text.contains("Reviews"))
+        {
+            (summary.rating_count, summary.review_count) = parse_rating_counts(&text);
+        }
+
+        // histogram is a list of the star labels `5★` to `1★`
+        // followed by a list of the counts
+        let lists = document
+            .select(ul_selector)
+            .map(|ul| {
+                ul.select(li_selector)
+                    .map(|li| li.text().collect::<String>())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|items| items.len() == 5)
+            .collect::<Vec<_>>();
+        let labels = lists.iter().position(|items| {
+            items
+                .iter()
+                .map(|label| label.trim().trim_end_matches('★').trim())
+                .eq(["5", "4", "3", "2", "1"])
+        });
+        if let Some(counts) = labels.and_then(|labels| lists.get(labels + 1..)) {
+            if let Some(counts) = counts.iter().find_map(|items| {
+                items
+                    .iter()
+                    .map(|count| parse_count(count))
+                    .collect::<Option<Vec<_>>>()
+            }) {
+                summary.stars = (1..=5)
+                    .rev()
+                    .zip(counts)
+                    .map(|(stars, count)| StarCount { stars, count })
+                    .collect();
+            }
+        }
+
+        // aspects are shown as a rating without a star image,
+        // followed by the name of the aspect
+        for elem in document.select(rating_selector) {
+            if elem.select(img_selector).next().is_some() {
+                continue;
+            }
+            let Some(rating) = elem
+                .first_child()
+                .and_then(|text| text.value().as_text())
+                .map(|text| text.trim())
+                .filter(|text| text.contains('.'))
+                .and_then(|text| text.parse::<f32>().ok())
+                .filter(|rating| (0.0..=5.0).contains(rating))
+            else {
+                continue;
+            };
+            let name = elem
+                .ancestors()
+                .take(3)
+                .filter_map(ElementRef::wrap)
+                .find_map(|ancestor| {
+                    let sibling = ancestor.next_siblings().find_map(ElementRef::wrap)?;
+                    let name = sibling.text().collect::<String>();
+                    let name = name.trim();
+                    let is_name = !name.is_empty()
+                        && name.split_whitespace().count() <= 3
+                        && name.chars().all(|c| c.is_alphabetic() || c.is_whitespace());
+                    is_name.then(|| name.to_string())
+                });
+            if let Some(name) = name {
+                if !summary.aspects.iter().any(|aspect| aspect.name == name) {
+                    summary.aspects.push(AspectRating { name, rating });
+                }
+            }
+        }
+
+        if summary.rating_count.is_none()
+            && summary.review_count.is_none()
+            && summary.stars.is_empty()
+            && summary.aspects.is_empty()
+        {
+            return None;
+        }
+        Some(summary)
+    }
+}
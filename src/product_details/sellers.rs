@@ -0,0 +1,184 @@
+use crate::product_details::{shown_pincode, Seller};
+use crate::{Error, FlipkartClient, Price, ProductRef, Result};
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// A seller's listing of a Product, from the list of all its sellers.
+pub struct SellerListing {
+    /// Name and rating of the seller.
+    pub seller: Seller,
+    /// Current price of the product from the seller.
+    pub current_price: Option<Price>,
+    /// Original price of the product from the seller.
+    pub original_price: Option<Price>,
+    /// Delivery estimate, like `Delivery by 12 Oct, Thursday`.
+    pub delivery: Option<String>,
+    /// Whether the listing is f-assured or not.
+    pub f_assured: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+/// All the sellers of a product on Flipkart.
+///
+/// Use `ProductSellers::fetch` method to get the sellers of a product.
+pub struct ProductSellers {
+    /// Product sold by the sellers
+    pub product: ProductRef,
+    /// URL of the sellers page
+    pub sellers_url: String,
+    /// Pincode the page shows the delivery estimates are for,
+    /// `None` if it doesn't show one
+    pub pincode: Option<String>,
+    /// Listings of the product by each seller
+    pub sellers: Vec<SellerListing>,
+}
+
+impl std::ops::Deref for ProductSellers {
+    type Target = Vec<SellerListing>;
+    fn deref(&self) -> &Self::Target {
+        &self.sellers
+    }
+}
+impl std::ops::DerefMut for ProductSellers {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.sellers
+    }
+}
+
+impl ProductSellers {
+    /// Fetches all the sellers of the product at the given url.
+    ///
+    /// ```rust,no_run
+    /// use std::error::Error;
+    /// use flipkart_scraper::{ProductSellers, Url};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let url = "https://www.flipkart.com/samsung-galaxy-f13-waterfall-blue-64-gb/p/itm583ef432b2b0c?pid=MOBGENJWBPFYJSFT";
+    ///     let sellers = ProductSellers::fetch(Url::parse(url)?).await;
+    ///     println!("{:#?}", sellers);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Fails with `Error::Parse` if the link has neither a product ID
+    /// nor an item ID.
+    ///
+    /// Builds a new client for every call, use `FlipkartClient::sellers`
+    /// when making multiple requests.
+    pub async fn fetch(url: Url) -> Result<Self> {
+        let client = FlipkartClient::new()?;
+        let url = client.resolve_url(url).await?;
+        let product = ProductRef::from_url(&url)
+            .ok_or_else(|| Error::Parse(format!("`{url}` is not a product link")))?;
+        client.sellers(&product).await
+    }
+
+    /// Fetches all the sellers of the product with the given product ID.
+    ///
+    /// Builds a new client for every call, use `FlipkartClient::sellers`
+    /// when making multiple requests.
+    pub async fn fetch_by_pid(pid: impl Into<String>) -> Result<Self> {
+        FlipkartClient::new()?
+            .sellers(&ProductRef::from_pid(pid))
+            .await
+    }
+
+    /// Parses the sellers of the product from the body
+    /// of its already fetched sellers page.
+    pub fn from_html(product: ProductRef, body: &str) -> Result<Self> {
+        let div_selector = &Selector::parse("div").unwrap();
+        let img_selector = &Selector::parse("img").unwrap();
+
+        if body.contains("has been moved or deleted") || body.contains("not right!") {
+            return Err(Error::ProductNotFound);
+        }
+
+        let document = Html::parse_document(body);
+
+        // rating is shown as the text of a div followed by a star image
+        let rating_of = |div: ElementRef| {
+            div.select(img_selector).next()?;
+            let rating = div.first_child()?.value().as_text()?;
+            rating.trim().parse::<f32>().ok()
+        };
+        let has_listing = |div: ElementRef| {
+            div.select(div_selector).any(|div| rating_of(div).is_some())
+                && div.text().any(|text| text.trim_start().starts_with('₹'))
+        };
+
+        // every listing is the smallest div holding both the seller's rating
+        // and the price
+        let sellers = document
+            .select(div_selector)
+            .filter(|div| has_listing(*div))
+            .filter(|div| {
+                !div.children()
+                    .filter_map(ElementRef::wrap)
+                    .any(|child| child.value().name() == "div" && has_listing(child))
+            })
+            .filter_map(|listing| {
+                let rating = listing.select(div_selector).find_map(rating_of);
+                let name = listing
+                    .text()
+                    .map(|text| text.trim())
+                    .find(|text| !text.is_empty())?;
+
+                let mut current_price = None;
+                let mut original_price = None;
+                for div in listing.select(div_selector) {
+                    let price_tag = div.first_child().and_then(|text| text.value().as_text());
+                    if price_tag.is_some_and(|text| text.starts_with('₹')) {
+                        let price_tag = div.text().collect::<String>();
+                        let price_tag = price_tag.strip_prefix('₹').unwrap();
+                        if price_tag.contains('₹') {
+                            continue;
+                        }
+                        let price = Price::parse(price_tag);
+                        if current_price.is_none() {
+                            current_price = price;
+                        } else {
+                            original_price = price;
+                            break;
+                        }
+                    }
+                }
+
+                let delivery = listing
+                    .select(div_selector)
+                    .find(|div| {
+                        div.first_child()
+                            .and_then(|text| text.value().as_text())
+                            .is_some_and(|text| text.contains("elivery"))
+                    })
+                    .map(|div| div.text().collect::<String>().trim().to_string());
+                let f_assured = listing.select(img_selector).any(|img| {
+                    img.value()
+                        .attr("src")
+                        .is_some_and(|src| src.contains("fa_62673a.png"))
+                });
+
+                Some(SellerListing {
+                    seller: Seller {
+                        name: name.to_string(),
+                        rating,
+                    },
+                    current_price,
+                    original_price,
+                    delivery,
+                    f_assured,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(ProductSellers {
+            sellers_url: product.sellers_url().to_string(),
+            pincode: shown_pincode(&document),
+            product,
+            sellers,
+        })
+    }
+}
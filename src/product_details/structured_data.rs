@@ -0,0 +1,284 @@
+use crate::{Currency, Price};
+use scraper::{Html, Selector};
+use serde_json::Value;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// schema.org structured data embedded in a product page
+/// as `application/ld+json` blocks.
+///
+/// Its values are parsed independently of the rest of the page,
+/// so they can be used to cross-check the other details.
+pub struct StructuredData {
+    /// Brand of the product.
+    pub brand: Option<String>,
+    /// Global Trade Item Number (EAN/UPC) of the product.
+    pub gtin: Option<String>,
+    /// Stock keeping unit of the product.
+    pub sku: Option<String>,
+    /// Aggregate customer rating of the product.
+    pub aggregate_rating: Option<AggregateRating>,
+    /// Offer the product is sold at.
+    pub offer: Option<StructuredOffer>,
+    /// All the schema.org objects found in the page, as they appear in it.
+    pub schemas: Vec<Value>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// schema.org `AggregateRating` of a Product.
+pub struct AggregateRating {
+    /// Average rating, out of 5.
+    pub rating: Option<f32>,
+    /// Number of ratings.
+    pub rating_count: Option<u32>,
+    /// Number of reviews.
+    pub review_count: Option<u32>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// schema.org `Offer` of a Product.
+pub struct StructuredOffer {
+    /// Price of the product, `None` if it is not in rupees.
+    pub price: Option<Price>,
+    /// Currency of the price, like `INR`.
+    pub currency: Option<String>,
+    /// Availability of the product, like `https://schema.org/InStock`.
+    pub availability: Option<String>,
+    /// Whether the availability means the product is in stock,
+    /// `None` if it is neither in stock nor out of stock.
+    pub in_stock: Option<bool>,
+}
+
+impl StructuredData {
+    /// Parses the `application/ld+json` blocks of a product page,
+    /// returns `None` if the page doesn't have any.
+    pub(crate) fn parse(document: &Html) -> Option<Self> {
+        let script_selector = &Selector::parse(r#"script[type="application/ld+json"]"#).unwrap();
+
+        let mut schemas = Vec::new();
+        for script in document.select(script_selector) {
+            let text = script.text().collect::<String>();
+            match serde_json::from_str(&text) {
+                Ok(Value::Array(values)) => schemas.extend(values),
+                Ok(value) => schemas.push(value),
+                Err(_) => continue,
+            }
+        }
+        // objects may also be nested in a `@graph`
+        let mut objects = Vec::new();
+        for schema in &schemas {
+            match schema.get("@graph").and_then(Value::as_array) {
+                Some(graph) => objects.extend(graph),
+                None => objects.push(schema),
+            }
+        }
+        if objects.is_empty() {
+            return None;
+        }
+
+        let mut data = StructuredData::default();
+        let product = objects.iter().find(|object| is_type(object, "Product"));
+        if let Some(product) = product {
+            data.brand = product.get("brand").and_then(|brand| {
+                brand
+                    .as_str()
+                    .or_else(|| brand.get("name")?.as_str())
+                    .map(String::from)
+            });
+            data.gtin = ["gtin13", "gtin", "gtin12", "gtin14", "gtin8"]
+                .into_iter()
+                .find_map(|key| text(product.get(key)?));
+            data.sku = product.get("sku").and_then(text);
+        }
+
+        data.aggregate_rating = product
+            .and_then(|product| product.get("aggregateRating"))
+            .or_else(|| {
+                objects
+                    .iter()
+                    .copied()
+                    .find(|object| is_type(object, "AggregateRating"))
+            })
+            .map(|rating| AggregateRating {
+                rating: rating
+                    .get("ratingValue")
+                    .and_then(number)
+                    .map(|rating| rating as f32),
+                rating_count: rating
+                    .get("ratingCount")
+                    .and_then(number)
+                    .map(|count| count as u32),
+                review_count: rating
+                    .get("reviewCount")
+                    .and_then(number)
+                    .map(|count| count as u32),
+            });
+
+        data.offer = product
+            .and_then(|product| product.get("offers"))
+            .or_else(|| {
+                objects
+                    .iter()
+                    .copied()
+                    .find(|object| is_type(object, "Offer"))
+            })
+            .map(|offers| match offers {
+                Value::Array(offers) => offers.first().unwrap_or(&Value::Null),
+                offer => offer,
+            })
+            .map(|offer| {
+                let availability = offer
+                    .get("availability")
+                    .and_then(Value::as_str)
+                    .map(String::from);
+                let currency = offer
+                    .get("priceCurrency")
+                    .and_then(Value::as_str)
+                    .map(String::from);
+                // prices without a currency are in rupees like the rest of the page
+                let in_rupees = currency.as_deref().map_or(true, |currency| {
+                    currency.eq_ignore_ascii_case(Currency::Inr.code())
+                });
+                StructuredOffer {
+                    price: offer
+                        .get("price")
+                        .or_else(|| offer.get("lowPrice"))
+                        .and_then(number)
+                        .filter(|_| in_rupees)
+                        .map(Price::from_decimal),
+                    currency,
+                    in_stock: availability.as_deref().and_then(|availability| {
+                        match availability.rsplit('/').next() {
+                            Some("InStock" | "LimitedAvailability" | "OnlineOnly") => Some(true),
+                            Some("OutOfStock" | "SoldOut" | "Discontinued") => Some(false),
+                            _ => None,
+                        }
+                    }),
+                    availability,
+                }
+            });
+
+        data.schemas = schemas;
+        Some(data)
+    }
+}
+
+/// Checks the `@type` of a schema.org object, which may be a list of types.
+fn is_type(object: &Value, schema_type: &str) -> bool {
+    match object.get("@type") {
+        Some(Value::String(name)) => name == schema_type,
+        Some(Value::Array(names)) => names.iter().any(|name| name == schema_type),
+        _ => false,
+    }
+}
+
+/// Reads a number given either as a number or as a string.
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(number) => number.replace(',', "").trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Reads a text given either as a string or as a number.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.to_owned()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Option<StructuredData> {
+        let html = format!(r#"<script type="application/ld+json">{json}</script>"#);
+        StructuredData::parse(&Html::parse_document(&html))
+    }
+
+    #[test]
+    fn reads_objects_nested_in_a_graph() {
+        let data = parse(
+            r#"{"@graph": [
+                {"@type": "BreadcrumbList"},
+                {"@type": ["Product", "Thing"], "brand": {"name": "Samsung"}, "sku": 123}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(data.brand.as_deref(), Some("Samsung"));
+        assert_eq!(data.sku.as_deref(), Some("123"));
+        assert_eq!(data.schemas.len(), 1);
+    }
+
+    #[test]
+    fn falls_back_through_gtin_keys() {
+        let data = parse(r#"{"@type": "Product", "gtin12": "012345678905", "gtin8": "1234"}"#);
+        assert_eq!(data.unwrap().gtin.as_deref(), Some("012345678905"));
+        let data = parse(r#"{"@type": "Product", "gtin13": 8801643000000}"#);
+        assert_eq!(data.unwrap().gtin.as_deref(), Some("8801643000000"));
+    }
+
+    #[test]
+    fn reads_numbers_given_as_strings() {
+        let data = parse(
+            r#"{"@type": "Product",
+                "aggregateRating": {"ratingValue": "4.3", "ratingCount": "12,345", "reviewCount": 1234},
+                "offers": {"@type": "Offer", "price": "12,999.50"}}"#,
+        )
+        .unwrap();
+        let rating = data.aggregate_rating.unwrap();
+        assert_eq!(rating.rating, Some(4.3));
+        assert_eq!(rating.rating_count, Some(12345));
+        assert_eq!(rating.review_count, Some(1234));
+        assert_eq!(data.offer.unwrap().price, Some(Price::from_paise(1299950)));
+    }
+
+    #[test]
+    fn reads_the_first_of_several_offers() {
+        let data = parse(
+            r#"{"@type": "Product", "offers": [
+                {"@type": "Offer", "price": 9999, "priceCurrency": "INR"},
+                {"@type": "Offer", "price": 10499, "priceCurrency": "INR"}
+            ]}"#,
+        )
+        .unwrap();
+        let offer = data.offer.unwrap();
+        assert_eq!(offer.price, Some(Price::from_rupees(9999)));
+        assert_eq!(offer.currency.as_deref(), Some("INR"));
+    }
+
+    #[test]
+    fn leaves_out_prices_in_other_currencies() {
+        let data = parse(r#"{"@type": "Offer", "price": 120, "priceCurrency": "USD"}"#).unwrap();
+        let offer = data.offer.unwrap();
+        assert_eq!(offer.price, None);
+        assert_eq!(offer.currency.as_deref(), Some("USD"));
+    }
+
+    #[test]
+    fn maps_availability_to_stock() {
+        let in_stock = |availability: &str| {
+            let json = format!(r#"{{"@type": "Offer", "availability": "{availability}"}}"#);
+            parse(&json).unwrap().offer.unwrap().in_stock
+        };
+        assert_eq!(in_stock("https://schema.org/InStock"), Some(true));
+        assert_eq!(
+            in_stock("http://schema.org/LimitedAvailability"),
+            Some(true)
+        );
+        assert_eq!(in_stock("OutOfStock"), Some(false));
+        assert_eq!(in_stock("https://schema.org/Discontinued"), Some(false));
+        assert_eq!(in_stock("https://schema.org/PreOrder"), None);
+    }
+
+    #[test]
+    fn ignores_pages_without_valid_blocks() {
+        assert!(parse("{not json").is_none());
+        assert!(StructuredData::parse(&Html::parse_document("<html></html>")).is_none());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::ProductRef;
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+
+/// Names of the dimensions products commonly vary in.
+const DIMENSIONS: [&str; 8] = [
+    "Color", "Colour", "Storage", "RAM", "Size", "Capacity", "Pattern", "Style",
+];
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// Variants of a Product along one dimension, like its colors.
+pub struct VariantGroup {
+    /// The dimension the variants differ in.
+    /// For example: `Color`, `Storage`, `RAM`, `Size` etc.
+    pub dimension: String,
+    /// The variants available.
+    pub options: Vec<Variant>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// A variant of a Product, like a particular color.
+pub struct Variant {
+    /// Label of the variant, like `Blue` or `128 GB`.
+    pub label: String,
+    /// URL to the variant.
+    pub url: Option<String>,
+    /// Identifiers of the variant parsed from its URL.
+    pub product_ref: Option<ProductRef>,
+    /// Whether it is the variant of the product being viewed.
+    pub selected: bool,
+    /// Whether the variant is in stock.
+    pub in_stock: bool,
+}
+
+impl VariantGroup {
+    /// Parses the variant selectors of a product page.
+    ///
+    /// `pid` is the product ID of the product being viewed,
+    /// used to find the selected variants.
+    pub(crate) fn parse(document: &Html, pid: Option<&str>) -> Vec<Self> {
+        let ul_selector = &Selector::parse("ul").unwrap();
+        let link_selector = &Selector::parse("a").unwrap();
+        let img_selector = &Selector::parse("img").unwrap();
+
+        let mut groups = Vec::<VariantGroup>::new();
+        for list in document.select(ul_selector) {
+            let items = list
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|item| item.value().name() == "li")
+                .collect::<Vec<_>>();
+
+            // swatches have ids like `swatch-0-color`, otherwise the list
+            // has to follow the name of a dimension
+            let swatch_dimension = items.iter().find_map(|item| {
+                let id = item.value().id()?.strip_prefix("swatch-")?;
+                id.split_once('-')
+                    .map(|(_, dimension)| dimension.to_string())
+            });
+            let label = list
+                .prev_siblings()
+                .find_map(ElementRef::wrap)
+                .map(|label| label.text().collect::<String>().trim().to_string())
+                .filter(|label| !label.is_empty() && label.len() <= 20);
+            let dimension = match (label, swatch_dimension) {
+                (Some(label), Some(_)) => label,
+                (Some(label), None) if DIMENSIONS.iter().any(|d| label.eq_ignore_ascii_case(d)) => {
+                    label
+                }
+                (None, Some(dimension)) => capitalize(&dimension),
+                _ => continue,
+            };
+
+            let options = items
+                .into_iter()
+                .filter_map(|item| {
+                    let label = item.text().collect::<String>().trim().to_string();
+                    let label = Some(label).filter(|label| !label.is_empty()).or_else(|| {
+                        item.select(img_selector)
+                            .find_map(|img| img.value().attr("alt"))
+                            .map(|alt| alt.trim().to_string())
+                    })?;
+                    let url = item
+                        .select(link_selector)
+                        .find_map(|link| link.value().attr("href"))
+                        .and_then(|href| {
+                            Url::parse("https://www.flipkart.com").ok()?.join(href).ok()
+                        });
+                    let product_ref = url.as_ref().and_then(ProductRef::from_url);
+                    let selected =
+                        pid.is_some() && product_ref.as_ref().and_then(|r| r.pid.as_deref()) == pid;
+                    let in_stock = url.is_some() && !label.to_lowercase().contains("out of stock");
+                    Some(Variant {
+                        label,
+                        url: url.map(String::from),
+                        product_ref,
+                        selected,
+                        in_stock,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            if options.iter().any(|option| option.url.is_some()) {
+                groups.push(VariantGroup { dimension, options });
+            }
+        }
+        groups
+    }
+}
+
+/// Capitalizes the first letter of the text.
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
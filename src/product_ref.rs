@@ -0,0 +1,151 @@
+use url::Url;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+/// Identifiers of a product parsed from its Flipkart URL.
+///
+/// Product URLs look like
+/// `https://www.flipkart.com/<slug>/p/<item id>?pid=<pid>&lid=<lid>&marketplace=FLIPKART`
+/// along with a bunch of tracking parameters.
+///
+/// ```rust
+/// use flipkart_scraper::{ProductRef, Url};
+///
+/// let url = Url::parse(
+///     "https://www.flipkart.com/samsung-galaxy-f13-waterfall-blue-64-gb/p/itm583ef432b2b0c?pid=MOBGENJWBPFYJSFT&lid=LSTMOBGENJWBPFYJSFTQZQJZN&marketplace=FLIPKART&srno=s_1_1&otracker=search",
+/// )?;
+/// let product = ProductRef::from_url(&url).unwrap();
+/// assert_eq!(product.pid.as_deref(), Some("MOBGENJWBPFYJSFT"));
+/// assert_eq!(
+///     product.canonical_url().as_str(),
+///     "https://www.flipkart.com/samsung-galaxy-f13-waterfall-blue-64-gb/p/itm583ef432b2b0c?pid=MOBGENJWBPFYJSFT&lid=LSTMOBGENJWBPFYJSFTQZQJZN&marketplace=FLIPKART",
+/// );
+/// # Ok::<(), url::ParseError>(())
+/// ```
+pub struct ProductRef {
+    /// Human readable part of the URL, like `samsung-galaxy-f13-waterfall-blue-64-gb`.
+    pub slug: Option<String>,
+    /// Item ID of the product, like `itm583ef432b2b0c`.
+    pub item_id: Option<String>,
+    /// Product ID, like `MOBGENJWBPFYJSFT`.
+    pub pid: Option<String>,
+    /// Listing ID, identifies the product sold by a particular seller.
+    pub lid: Option<String>,
+    /// Marketplace the product is listed on, like `FLIPKART` or `GROCERY`.
+    pub marketplace: Option<String>,
+}
+
+impl ProductRef {
+    /// Creates a reference to the product with the given product ID.
+    pub fn from_pid(pid: impl Into<String>) -> Self {
+        Self {
+            pid: Some(pid.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the listing ID, to select a particular seller's listing.
+    pub fn lid(mut self, lid: impl Into<String>) -> Self {
+        self.lid = Some(lid.into());
+        self
+    }
+
+    /// Sets the item ID of the product.
+    pub fn item_id(mut self, item_id: impl Into<String>) -> Self {
+        self.item_id = Some(item_id.into());
+        self
+    }
+
+    /// Parses the identifiers from a product URL.
+    ///
+    /// Returns `None` if the URL contains neither an item ID nor a product ID.
+    pub fn from_url(url: &Url) -> Option<Self> {
+        let mut product = ProductRef::default();
+
+        let segments = url.path_segments()?.collect::<Vec<_>>();
+        if let Some(p) = segments
+            .iter()
+            .position(|segment| *segment == "p" || *segment == "product-reviews")
+        {
+            product.slug = p
+                .checked_sub(1)
+                .and_then(|i| segments.get(i))
+                .filter(|slug| !slug.is_empty())
+                .map(|slug| slug.to_string());
+            product.item_id = segments
+                .get(p + 1)
+                .filter(|id| id.starts_with("itm"))
+                .map(|id| id.to_string());
+        }
+
+        for (key, value) in url.query_pairs() {
+            let field = match key.as_ref() {
+                "pid" => &mut product.pid,
+                "lid" => &mut product.lid,
+                "marketplace" => &mut product.marketplace,
+                _ => continue,
+            };
+            if !value.is_empty() {
+                *field = Some(value.into_owned());
+            }
+        }
+
+        if product.item_id.is_none() && product.pid.is_none() {
+            return None;
+        }
+        Some(product)
+    }
+
+    /// URL to the product without any tracking parameters.
+    ///
+    /// Flipkart only uses the slug for presentation and the item ID when
+    /// a product ID is given, placeholders are used for the ones missing.
+    pub fn canonical_url(&self) -> Url {
+        self.url_of("p")
+    }
+
+    /// URL to the reviews of the product without any tracking parameters.
+    pub fn reviews_url(&self) -> Url {
+        self.url_of("product-reviews")
+    }
+
+    /// URL to the list of all sellers of the product,
+    /// which needs the product ID to be known.
+    pub fn sellers_url(&self) -> Url {
+        let mut url = Url::parse("https://www.flipkart.com/sellers").expect("valid url");
+        self.append_params(&mut url);
+        url
+    }
+
+    /// URL to a page of the product, like `p` for its details
+    /// or `product-reviews` for its reviews.
+    fn url_of(&self, page: &str) -> Url {
+        let mut url = Url::parse("https://www.flipkart.com").expect("valid base url");
+        url.path_segments_mut()
+            .expect("base url")
+            .pop_if_empty()
+            .extend([
+                self.slug.as_deref().unwrap_or("product"),
+                page,
+                self.item_id.as_deref().unwrap_or("itm"),
+            ]);
+        self.append_params(&mut url);
+        url
+    }
+
+    /// Appends the identifiers, other than the slug and the item ID,
+    /// as query parameters to the url.
+    fn append_params(&self, url: &mut Url) {
+        let params = [
+            ("pid", &self.pid),
+            ("lid", &self.lid),
+            ("marketplace", &self.marketplace),
+        ];
+        for (key, value) in params
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value.as_deref()?)))
+        {
+            url.query_pairs_mut().append_pair(key, value);
+        }
+    }
+}
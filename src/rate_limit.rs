@@ -0,0 +1,124 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug)]
+/// Token bucket limiting the rate of requests.
+///
+/// The bucket holds up to `burst` tokens and is refilled at
+/// `requests_per_second` tokens per second, every request takes a token
+/// and waits for one to become available when the bucket is empty.
+///
+/// A limiter set on a `FlipkartClient` is shared by all of its clones,
+/// wrap it in an `Arc` and pass it to `FlipkartClientBuilder::rate_limiter`
+/// to share it between separately built clients too.
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `requests_per_second` requests on average,
+    /// with bursts of up to `burst` requests.
+    ///
+    /// Panics if `requests_per_second` is not a positive finite number,
+    /// `FlipkartClientBuilder::rate_limit` reports it as an error instead.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert!(
+            Self::is_valid_rate(requests_per_second),
+            "requests per second must be positive and finite"
+        );
+        let burst = f64::from(burst.max(1));
+        Self {
+            requests_per_second,
+            burst,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// Checks whether the limiter can be created with the rate.
+    pub(crate) fn is_valid_rate(requests_per_second: f64) -> bool {
+        requests_per_second > 0.0 && requests_per_second.is_finite()
+    }
+
+    /// Waits until a request is allowed to be made.
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.try_acquire(Instant::now()) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Takes a token if one is available at `now`, otherwise returns
+    /// the time until the next token is available.
+    fn try_acquire(&self, now: Instant) -> Option<Duration> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(self.burst);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.requests_per_second,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn created_at(limiter: &RateLimiter) -> Instant {
+        limiter.bucket.lock().unwrap().updated
+    }
+
+    #[test]
+    fn allows_a_burst_then_waits() {
+        let limiter = RateLimiter::new(2.0, 3);
+        let now = created_at(&limiter);
+        for _ in 0..3 {
+            assert_eq!(limiter.try_acquire(now), None);
+        }
+        assert_eq!(limiter.try_acquire(now), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn refills_at_the_rate_up_to_the_burst() {
+        let limiter = RateLimiter::new(4.0, 2);
+        let start = created_at(&limiter);
+        assert_eq!(limiter.try_acquire(start), None);
+        assert_eq!(limiter.try_acquire(start), None);
+
+        let later = start + Duration::from_millis(125);
+        assert_eq!(limiter.try_acquire(later), Some(Duration::from_millis(125)));
+        let later = start + Duration::from_millis(250);
+        assert_eq!(limiter.try_acquire(later), None);
+
+        // a long pause refills the bucket only up to the burst
+        let later = start + Duration::from_secs(60);
+        assert_eq!(limiter.try_acquire(later), None);
+        assert_eq!(limiter.try_acquire(later), None);
+        assert!(limiter.try_acquire(later).is_some());
+    }
+
+    #[test]
+    fn zero_burst_allows_one_request() {
+        let limiter = RateLimiter::new(1.0, 0);
+        let now = created_at(&limiter);
+        assert_eq!(limiter.try_acquire(now), None);
+        assert!(limiter.try_acquire(now).is_some());
+    }
+}
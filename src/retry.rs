@@ -0,0 +1,146 @@
+use crate::{Error, ErrorKind};
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+/// Policy deciding how failed requests are retried.
+///
+/// The delay before the n-th retry is `base_delay * 2^(n - 1)`, capped at
+/// `max_delay`, and then reduced by a random fraction of at most `jitter`
+/// so that parallel requests don't retry in lockstep.
+///
+/// ```rust
+/// use flipkart_scraper::{ErrorKind, FlipkartClient, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::default()
+///     .max_attempts(5)
+///     .base_delay(Duration::from_secs(1))
+///     .retry_on([ErrorKind::Blocked, ErrorKind::Status]);
+/// let client = FlipkartClient::builder().retry_policy(policy).build();
+/// ```
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+    retry_on: Vec<ErrorKind>,
+}
+
+impl Default for RetryPolicy {
+    /// Makes up to 3 attempts starting with a delay of 500ms, retrying
+    /// on `Http`, `Status` and `Blocked` errors.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.5,
+            retry_on: vec![ErrorKind::Http, ErrorKind::Status, ErrorKind::Blocked],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy which never retries.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Sets the maximum number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Sets the upper bound of the delay between two attempts.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the fraction (between `0.0` and `1.0`) of the delay
+    /// which is randomized.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the kinds of errors which are retried.
+    pub fn retry_on(mut self, kinds: impl IntoIterator<Item = ErrorKind>) -> Self {
+        self.retry_on = kinds.into_iter().collect();
+        self
+    }
+
+    /// Returns the delay to wait before the given attempt,
+    /// or `None` if the error should not be retried.
+    ///
+    /// `attempt` is the number of attempts already made.
+    pub(crate) fn retry_after(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.retry_on.contains(&error.kind()) {
+            return None;
+        }
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+        Some(backoff.mul_f64(1.0 - self.jitter * fastrand::f64()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_max_delay() {
+        let policy = RetryPolicy::default()
+            .max_attempts(10)
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .jitter(0.0);
+        let delays: Vec<_> = (1..6)
+            .map(|attempt| policy.retry_after(attempt, &Error::Blocked).unwrap())
+            .collect();
+        assert_eq!(delays, [1, 2, 4, 5, 5].map(Duration::from_secs).to_vec());
+    }
+
+    #[test]
+    fn jitter_only_shortens_the_delay() {
+        let policy = RetryPolicy::default()
+            .base_delay(Duration::from_secs(1))
+            .jitter(0.5);
+        for _ in 0..100 {
+            let delay = policy.retry_after(1, &Error::Blocked).unwrap();
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn stops_after_max_attempts() {
+        let policy = RetryPolicy::default().max_attempts(3);
+        assert!(policy.retry_after(2, &Error::Blocked).is_some());
+        assert!(policy.retry_after(3, &Error::Blocked).is_none());
+        assert!(RetryPolicy::none()
+            .retry_after(1, &Error::Blocked)
+            .is_none());
+    }
+
+    #[test]
+    fn retries_only_listed_kinds() {
+        let policy = RetryPolicy::default().retry_on([ErrorKind::Status]);
+        assert!(policy.retry_after(1, &Error::Status(503)).is_some());
+        assert!(policy.retry_after(1, &Error::Blocked).is_none());
+        assert!(RetryPolicy::default()
+            .retry_after(1, &Error::ProductNotFound)
+            .is_none());
+        assert!(RetryPolicy::default()
+            .retry_after(1, &Error::TooManyRedirects)
+            .is_none());
+    }
+}
@@ -0,0 +1,7 @@
+mod query;
+pub use query::*;
+mod review;
+pub use review::*;
+#[allow(clippy::module_inception)]
+mod reviews;
+pub use reviews::*;
@@ -0,0 +1,98 @@
+use crate::ProductRef;
+use url::Url;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Query for a page of reviews of a product on Flipkart.
+///
+/// `ProductRef`s convert into a query for the first page of reviews.
+///
+/// ```rust
+/// use flipkart_scraper::reviews::{ReviewSort, ReviewsQuery};
+/// use flipkart_scraper::ProductRef;
+///
+/// let query = ReviewsQuery::new(ProductRef::from_pid("MOBGENJWBPFYJSFT"))
+///     .sort(ReviewSort::MostRecent)
+///     .page(3);
+/// let url = query.url();
+/// assert!(url.path().contains("/product-reviews/"));
+/// assert!(url.as_str().contains("sortOrder=MOST_RECENT&page=3"));
+/// ```
+pub struct ReviewsQuery {
+    /// Product to get the reviews of.
+    pub product: ProductRef,
+    /// Page of the reviews, starting at 1.
+    pub page: u32,
+    /// Order of the reviews.
+    pub sort: ReviewSort,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+/// Order in which Flipkart sorts the reviews.
+pub enum ReviewSort {
+    /// Reviews voted most helpful first, the default order on Flipkart.
+    #[default]
+    MostHelpful,
+    /// Newest reviews first.
+    MostRecent,
+    /// Highest rated reviews first.
+    PositiveFirst,
+    /// Lowest rated reviews first.
+    NegativeFirst,
+}
+
+impl ReviewSort {
+    /// Value of the `sortOrder` URL parameter for the order.
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            ReviewSort::MostHelpful => "MOST_HELPFUL",
+            ReviewSort::MostRecent => "MOST_RECENT",
+            ReviewSort::PositiveFirst => "POSITIVE_FIRST",
+            ReviewSort::NegativeFirst => "NEGATIVE_FIRST",
+        }
+    }
+}
+
+impl ReviewsQuery {
+    /// Creates a query for the first page of reviews of the product.
+    pub fn new(product: ProductRef) -> Self {
+        Self {
+            product,
+            page: 1,
+            sort: ReviewSort::default(),
+        }
+    }
+
+    /// Sets the page of the reviews, starting at 1.
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = page.max(1);
+        self
+    }
+
+    /// Sets the order of the reviews.
+    pub fn sort(mut self, sort: ReviewSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Builds the URL of the reviews page for the query.
+    pub fn url(&self) -> Url {
+        let mut url = self.product.reviews_url();
+        if self.sort != ReviewSort::MostHelpful {
+            url.query_pairs_mut()
+                .append_pair("sortOrder", self.sort.as_param());
+        }
+        if self.page > 1 {
+            url.query_pairs_mut()
+                .append_pair("page", &self.page.to_string());
+        }
+        url
+    }
+}
+
+impl From<ProductRef> for ReviewsQuery {
+    fn from(product: ProductRef) -> Self {
+        Self::new(product)
+    }
+}
@@ -0,0 +1,24 @@
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Debug)]
+/// A customer review of a Product.
+pub struct Review {
+    /// Title of the review, like `Terrific purchase`.
+    pub title: Option<String>,
+    /// Text of the review.
+    pub body: String,
+    /// Rating given by the customer, from 1 to 5 stars.
+    pub rating: Option<u8>,
+    /// Name of the customer.
+    pub author: Option<String>,
+    /// Whether the customer bought the product on Flipkart.
+    pub certified_buyer: bool,
+    /// Location of the customer, like `Mumbai District`.
+    pub location: Option<String>,
+    /// When the review was posted, as shown on the page,
+    /// like `Mar, 2023` or `10 months ago`.
+    pub date: Option<String>,
+    /// Number of customers who found the review helpful.
+    pub helpful_votes: Option<u32>,
+    /// Number of customers who did not find the review helpful.
+    pub unhelpful_votes: Option<u32>,
+}
@@ -0,0 +1,186 @@
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashSet;
+
+use crate::{
+    client::Paginated,
+    parse::{parse_count, total_pages},
+    reviews::{Review, ReviewSort, ReviewsQuery},
+    Error, FlipkartClient, ProductRef, Result, Url,
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+/// A page of customer reviews of a product on Flipkart.
+///
+/// Use `ProductReviews::fetch` method to get the first page of reviews, or
+/// `FlipkartClient::reviews_stream` to walk through all the pages.
+pub struct ProductReviews {
+    /// Product the reviews are of
+    pub product: ProductRef,
+    /// URL of the reviews page
+    pub reviews_url: String,
+    /// Order of the reviews
+    pub sort: ReviewSort,
+    /// Page of the reviews, starting at 1
+    pub page: u32,
+    /// Total number of pages of reviews, if shown on the page
+    pub total_pages: Option<u32>,
+    /// List of reviews
+    pub reviews: Vec<Review>,
+}
+
+impl Paginated for ProductReviews {
+    fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+
+    fn is_empty(&self) -> bool {
+        self.reviews.is_empty()
+    }
+}
+
+impl std::ops::Deref for ProductReviews {
+    type Target = Vec<Review>;
+    fn deref(&self) -> &Self::Target {
+        &self.reviews
+    }
+}
+impl std::ops::DerefMut for ProductReviews {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.reviews
+    }
+}
+
+impl ProductReviews {
+    /// Fetches the first page of reviews of the product at the given url.
+    ///
+    /// ```rust,no_run
+    /// use std::error::Error;
+    /// use flipkart_scraper::{ProductReviews, Url};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let url = "https://www.flipkart.com/samsung-galaxy-f13-waterfall-blue-64-gb/p/itm583ef432b2b0c?pid=MOBGENJWBPFYJSFT";
+    ///     let reviews = ProductReviews::fetch(Url::parse(url)?).await;
+    ///     println!("{:#?}", reviews);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Fails with `Error::Parse` if the link has neither a product ID
+    /// nor an item ID.
+    ///
+    /// Builds a new client for every call, use `FlipkartClient::reviews`
+    /// when making multiple requests.
+    pub async fn fetch(url: Url) -> Result<Self> {
+        let client = FlipkartClient::new()?;
+        let url = client.resolve_url(url).await?;
+        let product = ProductRef::from_url(&url)
+            .ok_or_else(|| Error::Parse(format!("`{url}` is not a product link")))?;
+        client.reviews(product).await
+    }
+
+    /// Fetches the first page of reviews of the product with the given product ID.
+    ///
+    /// Builds a new client for every call, use `FlipkartClient::reviews`
+    /// when making multiple requests.
+    pub async fn fetch_by_pid(pid: impl Into<String>) -> Result<Self> {
+        FlipkartClient::new()?
+            .reviews(ProductRef::from_pid(pid))
+            .await
+    }
+
+    /// Parses the reviews for the query from the body
+    /// of its already fetched reviews page.
+    pub fn from_html(query: ReviewsQuery, body: &str) -> Result<Self> {
+        let div_selector = &Selector::parse("div").unwrap();
+        let img_selector = &Selector::parse("img").unwrap();
+        let p_selector = &Selector::parse("p").unwrap();
+        let span_selector = &Selector::parse("span").unwrap();
+
+        if body.contains("has been moved or deleted") || body.contains("not right!") {
+            return Err(Error::ProductNotFound);
+        }
+
+        let document = Html::parse_document(body);
+
+        // every review starts with a row containing its rating, shown as the
+        // text of a div followed by a star image, and its title
+        let mut seen = HashSet::new();
+        let reviews = document
+            .select(div_selector)
+            .filter(|div| div.select(img_selector).next().is_some())
+            .filter_map(|div| {
+                let rating = div.first_child()?.value().as_text()?;
+                let rating = rating.trim().parse::<u8>().ok()?;
+                (1..=5).contains(&rating).then_some((div, rating))
+            })
+            .filter_map(|(rating_elem, rating)| {
+                // the rating row holds the title and the review card holding it
+                // has the author and date paragraphs as well
+                let row = rating_elem.parent().and_then(ElementRef::wrap)?;
+                row.select(p_selector).next()?;
+                let card = row.parent().and_then(ElementRef::wrap)?;
+                if card.select(p_selector).count() < 3 || !seen.insert(card.id()) {
+                    return None;
+                }
+
+                let rows = card
+                    .children()
+                    .filter_map(ElementRef::wrap)
+                    .collect::<Vec<_>>();
+                let body = rows
+                    .get(1)?
+                    .text()
+                    .collect::<String>()
+                    .trim()
+                    .trim_end_matches("READ MORE")
+                    .trim()
+                    .to_string();
+
+                let mut paragraphs = card
+                    .select(p_selector)
+                    .map(|p| p.text().collect::<String>().trim().to_string())
+                    .filter(|text| !text.is_empty());
+                let mut review = Review {
+                    title: paragraphs.next(),
+                    body,
+                    rating: Some(rating),
+                    ..Default::default()
+                };
+                for text in paragraphs {
+                    if let Some(location) = text.strip_prefix("Certified Buyer") {
+                        review.certified_buyer = true;
+                        let location =
+                            location.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+                        review.location = Some(location.to_string()).filter(|l| !l.is_empty());
+                    } else if review.author.is_none() {
+                        review.author = Some(text);
+                    } else {
+                        review.date = Some(text);
+                    }
+                }
+
+                // votes are the only numbers in the card, helpful ones first
+                let mut votes = rows
+                    .iter()
+                    .skip(2)
+                    .flat_map(|row| row.select(span_selector))
+                    .filter_map(|span| parse_count(&span.text().collect::<String>()));
+                review.helpful_votes = votes.next();
+                review.unhelpful_votes = votes.next();
+
+                Some(review)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(ProductReviews {
+            reviews_url: query.url().to_string(),
+            product: query.product,
+            sort: query.sort,
+            page: query.page,
+            total_pages: total_pages(&document),
+            reviews,
+        })
+    }
+}
@@ -1,2 +1,5 @@
+mod query;
+pub use query::*;
+#[allow(clippy::module_inception)]
 mod search;
 pub use search::*;
@@ -0,0 +1,303 @@
+use crate::{Error, Result};
+use url::Url;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Query to search products on Flipkart.
+///
+/// The setters adjust the filters to values Flipkart accepts, fields set
+/// directly are checked when building the URL.
+///
+/// Strings convert into a query for the first page of results,
+/// without any filters.
+///
+/// ```rust
+/// use flipkart_scraper::search::{SearchQuery, SortOrder};
+///
+/// let query = SearchQuery::new("washing machine")
+///     .sort(SortOrder::PriceLowToHigh)
+///     .page(2)
+///     .max_price(30000)
+///     .brand("Samsung")
+///     .brand("LG")
+///     .min_rating(4)
+///     .f_assured(true);
+/// let url = query.url().unwrap();
+/// assert!(url.as_str().contains("sort=price_asc"));
+/// assert!(url.as_str().contains("facets.brand%5B%5D%3DSamsung"));
+/// ```
+pub struct SearchQuery {
+    /// Text searched for.
+    pub query: String,
+    /// Page of the search results, starting at 1.
+    pub page: u32,
+    /// Order of the search results.
+    pub sort: SortOrder,
+    /// Minimum price (in rupees) of the products.
+    pub min_price: Option<u32>,
+    /// Maximum price (in rupees) of the products.
+    pub max_price: Option<u32>,
+    /// Only include products from these brands, all brands if empty.
+    pub brands: Vec<String>,
+    /// Minimum customer rating (1 to 4 stars) of the products.
+    pub min_rating: Option<u8>,
+    /// Only include Flipkart Assured products.
+    pub f_assured: bool,
+    /// Minimum discount (in percent, multiple of 10 up to 70) on the products.
+    pub min_discount: Option<u8>,
+    /// Include products which are out of stock.
+    pub include_out_of_stock: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+/// Order in which Flipkart sorts the search results.
+pub enum SortOrder {
+    /// Most relevant products first, the default order on Flipkart.
+    #[default]
+    Relevance,
+    /// Most popular products first.
+    Popularity,
+    /// Cheapest products first.
+    PriceLowToHigh,
+    /// Most expensive products first.
+    PriceHighToLow,
+    /// Newest products first.
+    Newest,
+}
+
+impl SortOrder {
+    /// Value of the `sort` URL parameter for the order.
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            SortOrder::Relevance => "relevance",
+            SortOrder::Popularity => "popularity",
+            SortOrder::PriceLowToHigh => "price_asc",
+            SortOrder::PriceHighToLow => "price_desc",
+            SortOrder::Newest => "recency_desc",
+        }
+    }
+}
+
+impl SearchQuery {
+    /// Creates a query for the first page of results of the given text.
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            page: 1,
+            sort: SortOrder::default(),
+            min_price: None,
+            max_price: None,
+            brands: Vec::new(),
+            min_rating: None,
+            f_assured: false,
+            min_discount: None,
+            include_out_of_stock: false,
+        }
+    }
+
+    /// Sets the order of the search results.
+    pub fn sort(mut self, sort: SortOrder) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Sets the page of the search results, starting at 1.
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = page.max(1);
+        self
+    }
+
+    /// Only include products costing at least `min_price` rupees.
+    pub fn min_price(mut self, min_price: u32) -> Self {
+        self.min_price = Some(min_price);
+        self
+    }
+
+    /// Only include products costing at most `max_price` rupees.
+    pub fn max_price(mut self, max_price: u32) -> Self {
+        self.max_price = Some(max_price);
+        self
+    }
+
+    /// Adds a brand to include, can be called multiple times
+    /// to include multiple brands.
+    pub fn brand(mut self, brand: impl Into<String>) -> Self {
+        self.brands.push(brand.into());
+        self
+    }
+
+    /// Only include products rated at least `min_rating` stars,
+    /// Flipkart filters from 1 up to 4 stars.
+    pub fn min_rating(mut self, min_rating: u8) -> Self {
+        self.min_rating = Some(min_rating.clamp(1, 4));
+        self
+    }
+
+    /// Sets whether to only include Flipkart Assured products.
+    pub fn f_assured(mut self, f_assured: bool) -> Self {
+        self.f_assured = f_assured;
+        self
+    }
+
+    /// Only include products discounted by at least `min_discount` percent.
+    ///
+    /// Flipkart filters by multiples of 10 from 10% up to 70%,
+    /// so the value is rounded down to a multiple of 10 and capped at 70%.
+    /// Building the URL fails for values below 10%.
+    pub fn min_discount(mut self, min_discount: u8) -> Self {
+        self.min_discount = Some((min_discount / 10 * 10).min(70));
+        self
+    }
+
+    /// Sets whether to include products which are out of stock.
+    pub fn include_out_of_stock(mut self, include_out_of_stock: bool) -> Self {
+        self.include_out_of_stock = include_out_of_stock;
+        self
+    }
+
+    /// Filters of the query as values of the `p[]` URL parameter.
+    fn facets(&self) -> Vec<String> {
+        let mut facets = Vec::new();
+        if self.min_price.is_some() || self.max_price.is_some() {
+            let bound = |price: Option<u32>, default: &str| {
+                price.map_or_else(|| default.to_string(), |price| price.to_string())
+            };
+            facets.push(format!(
+                "facets.price_range.from={}",
+                bound(self.min_price, "Min")
+            ));
+            facets.push(format!(
+                "facets.price_range.to={}",
+                bound(self.max_price, "Max")
+            ));
+        }
+        for brand in &self.brands {
+            facets.push(format!("facets.brand[]={brand}"));
+        }
+        if let Some(rating) = self.min_rating {
+            facets.push(format!("facets.rating[]={rating}★ & above"));
+        }
+        if self.f_assured {
+            facets.push(String::from("facets.fulfilled_by[]=Flipkart Assured"));
+        }
+        if let Some(discount) = self.min_discount {
+            facets.push(format!("facets.discount_range_v1[]={discount}% or more"));
+        }
+        if self.include_out_of_stock {
+            facets.push(String::from("facets.availability[]=Include Out of Stock"));
+        }
+        facets
+    }
+
+    /// Builds the URL of the search page for the query.
+    ///
+    /// Fails with `Error::InvalidQuery` if the minimum price is above
+    /// the maximum price, the minimum rating is not from 1 to 4 stars or
+    /// the minimum discount is not a multiple of 10 from 10% up to 70%.
+    pub fn url(&self) -> Result<Url> {
+        if let (Some(min_price), Some(max_price)) = (self.min_price, self.max_price) {
+            if min_price > max_price {
+                return Err(Error::InvalidQuery(format!(
+                    "minimum price {min_price} is above maximum price {max_price}"
+                )));
+            }
+        }
+        if let Some(rating) = self.min_rating.filter(|rating| !(1..=4).contains(rating)) {
+            return Err(Error::InvalidQuery(format!(
+                "minimum rating {rating} is not from 1 to 4 stars"
+            )));
+        }
+        if let Some(discount) = self
+            .min_discount
+            .filter(|discount| !(10..=70).contains(discount) || discount % 10 != 0)
+        {
+            return Err(Error::InvalidQuery(format!(
+                "minimum discount {discount}% is not a multiple of 10 from 10% up to 70%"
+            )));
+        }
+        let mut url = Url::parse_with_params(
+            "https://www.flipkart.com/search?marketplace=FLIPKART",
+            &[("q", &self.query)],
+        )?;
+        if self.sort != SortOrder::Relevance {
+            url.query_pairs_mut()
+                .append_pair("sort", self.sort.as_param());
+        }
+        for facet in self.facets() {
+            url.query_pairs_mut().append_pair("p[]", &facet);
+        }
+        if self.page > 1 {
+            url.query_pairs_mut()
+                .append_pair("page", &self.page.to_string());
+        }
+        Ok(url)
+    }
+}
+
+impl From<String> for SearchQuery {
+    fn from(query: String) -> Self {
+        Self::new(query)
+    }
+}
+
+impl From<&str> for SearchQuery {
+    fn from(query: &str) -> Self {
+        Self::new(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[test]
+    fn min_discount_rounds_down_and_caps() {
+        assert_eq!(
+            SearchQuery::new("tv").min_discount(35).min_discount,
+            Some(30)
+        );
+        assert_eq!(
+            SearchQuery::new("tv").min_discount(90).min_discount,
+            Some(70)
+        );
+    }
+
+    #[test]
+    fn rejects_min_discount_below_ten() {
+        let error = SearchQuery::new("tv").min_discount(5).url().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidQuery);
+    }
+
+    #[test]
+    fn rejects_discounts_flipkart_does_not_filter_by() {
+        let mut query = SearchQuery::new("tv");
+        for discount in [0, 35, 80] {
+            query.min_discount = Some(discount);
+            assert_eq!(query.url().unwrap_err().kind(), ErrorKind::InvalidQuery);
+        }
+        query.min_discount = Some(70);
+        assert!(query.url().is_ok());
+    }
+
+    #[test]
+    fn rejects_ratings_flipkart_does_not_filter_by() {
+        let mut query = SearchQuery::new("tv");
+        for rating in [0, 5] {
+            query.min_rating = Some(rating);
+            assert_eq!(query.url().unwrap_err().kind(), ErrorKind::InvalidQuery);
+        }
+        query.min_rating = Some(1);
+        assert!(query.url().is_ok());
+        assert!(SearchQuery::new("tv").min_rating(5).url().is_ok());
+    }
+
+    #[test]
+    fn rejects_inverted_price_range() {
+        let query = SearchQuery::new("tv").min_price(5000).max_price(1000);
+        assert_eq!(query.url().unwrap_err().kind(), ErrorKind::InvalidQuery);
+        let query = SearchQuery::new("tv").min_price(1000).max_price(1000);
+        assert!(query.url().is_ok());
+    }
+}
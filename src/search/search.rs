@@ -1,8 +1,11 @@
-use eyre::Result;
-use reqwest::Client;
 use scraper::{Html, Selector};
 
-use crate::ProductDetails;
+use crate::{
+    client::Paginated,
+    parse::{parse_count, parse_rating_counts, total_pages},
+    search::{SearchQuery, SortOrder},
+    FlipkartClient, Price, ProductDetails, ProductImage, ProductRef, Result,
+};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Default)]
@@ -12,12 +15,26 @@ pub struct SearchResult {
     pub product_name: String,
     /// Link to the product
     pub product_link: String,
-    /// URL to the thumbnail of the product
-    pub thumbnail: String,
+    /// Identifiers of the product parsed from its link
+    pub product_ref: Option<ProductRef>,
+    /// Thumbnail of the product
+    pub thumbnail: ProductImage,
     /// Current price of the product
-    pub current_price: Option<i32>,
+    pub current_price: Option<Price>,
     /// Original price of the product
-    pub original_price: Option<i32>,
+    pub original_price: Option<Price>,
+    /// Customer rating of the product, out of 5
+    pub rating: Option<f32>,
+    /// Number of customer ratings of the product
+    pub rating_count: Option<u32>,
+    /// Number of customer reviews of the product
+    pub review_count: Option<u32>,
+    /// Whether the result is a paid placement
+    pub sponsored: bool,
+    /// Whether it is f-assured product or not
+    pub f_assured: bool,
+    /// Discount on the product in percent, as displayed in the results
+    pub discount_percent: Option<u8>,
 }
 
 impl SearchResult {
@@ -26,6 +43,16 @@ impl SearchResult {
         let product_link = url::Url::parse(&self.product_link)?;
         ProductDetails::fetch(product_link).await
     }
+
+    /// Get detailed information about the searched product
+    /// using the given client.
+    ///
+    /// Prefer this over `fetch_product` when fetching many products
+    /// so that the requests share the client's connections and rate limit.
+    pub async fn fetch_product_with(&self, client: &FlipkartClient) -> Result<ProductDetails> {
+        let product_link = url::Url::parse(&self.product_link)?;
+        client.product(product_link).await
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -38,10 +65,26 @@ pub struct ProductSearch {
     pub query: String,
     /// URL of the search query
     pub query_url: String,
+    /// Order of the search results
+    pub sort: SortOrder,
+    /// Page of the search results, starting at 1
+    pub page: u32,
+    /// Total number of pages of search results, if shown on the page
+    pub total_pages: Option<u32>,
     /// List of search results
     pub results: Vec<SearchResult>,
 }
 
+impl Paginated for ProductSearch {
+    fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+
+    fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
 impl std::ops::Deref for ProductSearch {
     type Target = Vec<SearchResult>;
     fn deref(&self) -> &Self::Target {
@@ -55,17 +98,22 @@ impl std::ops::DerefMut for ProductSearch {
 }
 
 impl ProductSearch {
+    /// Parses the search results from the body of an already fetched search page.
     pub fn search_doc(query: String, body: String) -> Result<Self> {
-        let search_url = url::Url::parse_with_params(
-            "https://www.flipkart.com/search?marketplace=FLIPKART",
-            &[("q", query.to_owned())],
-        )?;
+        Self::from_html(SearchQuery::new(query), &body)
+    }
+
+    /// Parses the search results for the query from the body
+    /// of its already fetched search page.
+    pub fn from_html(query: SearchQuery, body: &str) -> Result<Self> {
+        let search_url = query.url()?;
 
         let div_selector = &Selector::parse("div").unwrap();
         let img_selector = &Selector::parse("img").unwrap();
         let link_selector = &Selector::parse("a").unwrap();
+        let span_selector = &Selector::parse("span").unwrap();
 
-        let document = Html::parse_document(&body);
+        let document = Html::parse_document(body);
 
         let search_results = document
             .select(div_selector)
@@ -120,131 +168,91 @@ impl ProductSearch {
                             if price_tag.contains('₹') {
                                 continue;
                             }
-                            let price = price_tag.replace(',', "");
                             if current_price.is_none() {
-                                current_price = price.parse::<i32>().ok();
+                                current_price = Price::parse(price_tag);
                             } else {
-                                original_price = price.parse::<i32>().ok();
+                                original_price = Price::parse(price_tag);
                                 break;
                             }
                         }
                     }
                 }
 
-                Some(SearchResult {
-                    product_name: name.into(),
-                    product_link,
-                    thumbnail: thumbnail.into(),
-                    current_price,
-                    original_price,
-                })
-            })
-            .collect::<Vec<_>>();
-
-        Ok(ProductSearch {
-            query,
-            query_url: search_url.to_string(),
-            results: search_results,
-        })
-    }
+                // rating is shown as the text of a div followed by a star image
+                let rating = product
+                    .select(div_selector)
+                    .filter(|div| div.select(img_selector).next().is_some())
+                    .filter_map(|div| div.text().next()?.trim().parse::<f32>().ok())
+                    .find(|rating| (0.0..=5.0).contains(rating));
 
-    /// Searchs the query for a product on Flipkart.
-    pub async fn search(query: String) -> Result<Self> {
-        let search_url = url::Url::parse_with_params(
-            "https://www.flipkart.com/search?marketplace=FLIPKART",
-            &[("q", query.to_owned())],
-        )?;
-
-        let div_selector = &Selector::parse("div").unwrap();
-        let img_selector = &Selector::parse("img").unwrap();
-        let link_selector = &Selector::parse("a").unwrap();
-
-        let client = Client::builder()
-            .default_headers(crate::build_headers())
-            .build()?;
-
-        let webpage = client.get(search_url.to_owned()).send().await?;
-        let body = webpage.text().await?;
-        let document = Html::parse_document(&body);
-
-        let search_results = document
-            .select(div_selector)
-            .filter(|div| div.value().attr("data-id").is_some())
-            .filter_map(|product| {
-                let mut link_iter = product.select(link_selector);
-                let mut link_elem = link_iter.next()?;
-                let product_link: String = link_elem.value().attr("href").map(|link| {
-                    if link.starts_with('/') {
-                        String::from("https://flipkart.com") + link
-                    } else {
-                        link.into()
+                // counts are either shown as `12,345 Ratings & 1,234 Reviews`
+                // or just the number of ratings in brackets like `(12,345)`
+                let mut rating_count = None;
+                let mut review_count = None;
+                for span in product.select(span_selector) {
+                    let text = span.text().collect::<String>();
+                    let text = text.trim();
+                    if text.contains("Rating") {
+                        (rating_count, review_count) = parse_rating_counts(text);
+                        break;
                     }
-                })?;
-                let thumbnail = link_elem
-                    .select(img_selector)
-                    .next()
-                    .and_then(|img| img.value().attr("src"))?;
-
-                let name_section = link_elem.last_child()?.value().as_element()?.classes();
-                // select using the selector of classes
-                let class_selector = &Selector::parse(
-                    &name_section
-                        .map(|sel| String::from('.') + sel)
-                        .collect::<String>(),
-                )
-                .ok()?;
-                let name = link_elem
-                    .select(class_selector)
-                    .next()
-                    .and_then(|name_elem| {
-                        let name = name_elem.text().next();
-                        if name == Some("Sponsored") {
-                            name_elem.text().nth(1)
-                        } else {
-                            name
-                        }
-                    })
-                    .or_else(|| {
-                        link_elem = link_iter.next()?;
-                        link_elem.value().attr("title")
-                    })
-                    .or_else(|| link_elem.text().next())?;
-
-                let mut current_price = None;
-                let mut original_price = None;
-                for div in product.select(div_selector) {
-                    if let Some(price_tag) = div.text().next() {
-                        if price_tag.starts_with('₹') {
-                            let price_tag = div.text().collect::<String>();
-                            let price_tag = price_tag.strip_prefix('₹').unwrap();
-                            if price_tag.contains('₹') {
-                                continue;
-                            }
-                            let price = price_tag.replace(',', "");
-                            if current_price.is_none() {
-                                current_price = price.parse::<i32>().ok();
-                            } else {
-                                original_price = price.parse::<i32>().ok();
-                                break;
-                            }
-                        }
+                    if rating.is_some() && rating_count.is_none() {
+                        rating_count = text
+                            .strip_prefix('(')
+                            .and_then(|text| text.strip_suffix(')'))
+                            .and_then(parse_count);
                     }
                 }
 
+                let sponsored = product.text().any(|text| text.trim() == "Sponsored");
+                let f_assured = product.select(img_selector).any(|img| {
+                    img.value()
+                        .attr("src")
+                        .is_some_and(|src| src.contains("fa_62673a.png"))
+                });
+                let discount_percent = product.text().find_map(|text| {
+                    let (percent, _) = text.trim().split_once("% off")?;
+                    percent.parse::<u8>().ok()
+                });
+
+                let product_ref = url::Url::parse(&product_link)
+                    .ok()
+                    .and_then(|link| ProductRef::from_url(&link));
+
                 Some(SearchResult {
                     product_name: name.into(),
                     product_link,
+                    product_ref,
                     thumbnail: thumbnail.into(),
                     current_price,
                     original_price,
+                    rating,
+                    rating_count,
+                    review_count,
+                    sponsored,
+                    f_assured,
+                    discount_percent,
                 })
             })
             .collect::<Vec<_>>();
 
+        let total_pages = total_pages(&document);
+
         Ok(ProductSearch {
-            query,
+            query: query.query,
             query_url: search_url.to_string(),
+            sort: query.sort,
+            page: query.page,
+            total_pages,
             results: search_results,
         })
     }
+
+    /// Searchs the query for a product on Flipkart.
+    ///
+    /// Builds a new client for every call, use `FlipkartClient::search`
+    /// when making multiple requests.
+    pub async fn search(query: String) -> Result<Self> {
+        FlipkartClient::new()?.search(query).await
+    }
 }
@@ -0,0 +1,160 @@
+mod common;
+
+use common::Stub;
+use flipkart_scraper::search::SearchQuery;
+use flipkart_scraper::{ErrorKind, FlipkartClient, ProductSearch, Result, RetryPolicy};
+use futures_util::StreamExt;
+use std::time::Duration;
+
+const ERROR_PAGE: &str = "<html><body>Internal Server Error</body></html>";
+
+fn retrying() -> RetryPolicy {
+    RetryPolicy::default().base_delay(Duration::ZERO)
+}
+
+/// Search page with a single result and the given pagination text.
+fn results_page(pagination: &str) -> String {
+    format!(
+        r#"<html><body><div data-id="MOB1"><a href="/x/p/itm1?pid=MOB1"><div><img src="t.jpg"></div><div class="n">Phone X</div></a><div>₹9,999</div></div><span>{pagination}</span></body></html>"#
+    )
+}
+
+/// Collects the pages `search_pages` yields and the requested page numbers.
+async fn search_pages(
+    stub: Stub,
+    query: SearchQuery,
+    max_pages: Option<u32>,
+) -> (Vec<Result<ProductSearch>>, Vec<String>) {
+    let pages = stub.client().search_pages(query, max_pages).collect().await;
+    let requested = stub
+        .requests()
+        .iter()
+        .map(|url| {
+            // the first page is requested without a page number
+            url.query_pairs()
+                .find(|(key, _)| key == "page")
+                .map_or_else(|| "1".into(), |(_, page)| page.into_owned())
+        })
+        .collect();
+    (pages, requested)
+}
+
+#[tokio::test]
+async fn error_text_on_a_success_page_is_not_blocked() {
+    let body = "<html><body><p>Internal Server Error</p></body></html>";
+    let stub = Stub::default().respond(200, body);
+    let search = stub.client().search("internal server error").await.unwrap();
+    assert!(search.results.is_empty());
+}
+
+#[tokio::test]
+async fn server_errors_are_retried() {
+    let stub = Stub::default()
+        .respond(500, ERROR_PAGE)
+        .respond(503, "")
+        .respond(200, "<html></html>");
+    stub.client_with(retrying()).search("phone").await.unwrap();
+    assert_eq!(stub.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let stub = Stub::default().respond(500, ERROR_PAGE);
+    let error = stub
+        .client_with(retrying().max_attempts(2))
+        .search("phone")
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Blocked);
+    assert_eq!(stub.requests().len(), 2);
+
+    let stub = Stub::default().respond(502, "");
+    let error = stub.client().search("phone").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Status);
+    assert_eq!(stub.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn error_page_served_with_success_status_is_retried_for_products() {
+    let stub = Stub::default()
+        .respond(200, ERROR_PAGE)
+        .respond(200, "<html></html>");
+    let error = stub
+        .client_with(retrying())
+        .product_by_pid("MOBX")
+        .await
+        .unwrap_err();
+    // the second page is not an error page, so it is not retried
+    assert_eq!(error.kind(), ErrorKind::Parse);
+    assert_eq!(stub.requests().len(), 2);
+}
+
+#[test]
+fn invalid_rate_limit_fails_to_build() {
+    for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let error = FlipkartClient::builder()
+            .rate_limit(rate, 1)
+            .build()
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidConfig);
+    }
+    FlipkartClient::builder()
+        .rate_limit(0.5, 1)
+        .build()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn pages_stop_after_the_last_page() {
+    let stub = Stub::default()
+        .respond(200, results_page("Page 1 of 2"))
+        .respond(200, results_page("Page 2 of 2"));
+    let (pages, requested) = search_pages(stub, "phone".into(), None).await;
+    assert_eq!(pages.len(), 2);
+    assert!(pages.iter().all(|page| page.is_ok()));
+    assert_eq!(requested, ["1", "2"]);
+}
+
+#[tokio::test]
+async fn pages_start_from_the_query_page() {
+    let stub = Stub::default().respond(200, results_page("Page 3 of 3"));
+    let query = SearchQuery::new("phone").page(3);
+    let (pages, requested) = search_pages(stub, query, None).await;
+    assert_eq!(pages[0].as_ref().unwrap().page, 3);
+    assert_eq!(requested, ["3"]);
+}
+
+#[tokio::test]
+async fn pages_stop_at_an_empty_page() {
+    let stub = Stub::default()
+        .respond(200, results_page(""))
+        .respond(200, results_page(""))
+        .respond(200, "<html></html>");
+    let (pages, requested) = search_pages(stub, "phone".into(), None).await;
+    assert_eq!(pages.len(), 2);
+    assert_eq!(requested, ["1", "2", "3"]);
+}
+
+#[tokio::test]
+async fn pages_stop_after_max_pages() {
+    let stub = Stub::default().respond(200, results_page("Page 1 of 12"));
+    let (pages, requested) = search_pages(stub, "phone".into(), Some(3)).await;
+    assert_eq!(pages.len(), 3);
+    assert_eq!(requested, ["1", "2", "3"]);
+
+    let stub = Stub::default().respond(200, results_page(""));
+    let (pages, _) = search_pages(stub, "phone".into(), Some(0)).await;
+    assert!(pages.is_empty());
+}
+
+#[tokio::test]
+async fn pages_stop_after_an_error() {
+    let stub = Stub::default()
+        .respond(200, results_page(""))
+        .respond(503, "");
+    let (pages, requested) = search_pages(stub, "phone".into(), None).await;
+    assert!(pages[0].is_ok());
+    assert_eq!(pages[1].as_ref().unwrap_err().kind(), ErrorKind::Status);
+    assert_eq!(pages.len(), 2);
+    assert_eq!(requested, ["1", "2"]);
+}
@@ -0,0 +1,77 @@
+//! Helpers shared by the integration tests.
+//!
+//! Fixtures in `tests/fixtures` are minimal hand-written pages reproducing
+//! the markup the parsers rely on, they are not saved Flipkart pages.
+
+// every test crate uses only some of the helpers
+#![allow(dead_code)]
+
+use flipkart_scraper::{async_trait, Fetcher, FlipkartClient, Page, Result, RetryPolicy, Url};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Reads a fixture from `tests/fixtures`.
+pub fn fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+    std::fs::read_to_string(path).unwrap()
+}
+
+#[derive(Clone, Default)]
+/// `Fetcher` answering requests with queued responses, in order,
+/// repeating the last one, and recording the requested URLs.
+pub struct Stub {
+    state: Arc<Mutex<StubState>>,
+}
+
+#[derive(Default)]
+struct StubState {
+    responses: VecDeque<(u16, String)>,
+    requests: Vec<Url>,
+}
+
+impl Stub {
+    /// Queues a response.
+    pub fn respond(self, status: u16, body: impl Into<String>) -> Self {
+        let response = (status, body.into());
+        self.state.lock().unwrap().responses.push_back(response);
+        self
+    }
+
+    /// URLs requested so far.
+    pub fn requests(&self) -> Vec<Url> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Client fetching with this stub and the given retry policy.
+    pub fn client_with(&self, retry_policy: RetryPolicy) -> FlipkartClient {
+        FlipkartClient::builder()
+            .fetcher(self.clone())
+            .retry_policy(retry_policy)
+            .build()
+            .unwrap()
+    }
+
+    /// Client fetching with this stub, without retries.
+    pub fn client(&self) -> FlipkartClient {
+        self.client_with(RetryPolicy::none())
+    }
+}
+
+#[async_trait]
+impl Fetcher for Stub {
+    async fn fetch(&self, url: &Url) -> Result<Page> {
+        let mut state = self.state.lock().unwrap();
+        state.requests.push(url.clone());
+        let (status, body) = match state.responses.len() {
+            0 => panic!("no response queued for {url}"),
+            1 => state.responses[0].clone(),
+            _ => state.responses.pop_front().unwrap(),
+        };
+        Ok(Page {
+            url: url.clone(),
+            redirects: Vec::new(),
+            status,
+            body,
+        })
+    }
+}
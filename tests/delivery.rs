@@ -0,0 +1,90 @@
+mod common;
+
+use common::{fixture, Stub};
+use flipkart_scraper::{Price, ProductDetails, ProductRef, Url};
+
+const PRODUCT_URL: &str = "https://www.flipkart.com/phone-x/p/itm1?pid=MOBX";
+
+#[test]
+fn parses_delivery() {
+    let url = Url::parse(PRODUCT_URL).unwrap();
+    let details = ProductDetails::from_html(url, &fixture("delivery.html")).unwrap();
+
+    let delivery = details.delivery.unwrap();
+    assert_eq!(delivery.pincode.as_deref(), Some("560001"));
+    assert!(delivery.serviceable);
+    assert_eq!(
+        delivery.estimate.as_deref(),
+        Some("Delivery by 12 Oct, Thursday")
+    );
+    // the striked out charge is waived
+    assert_eq!(delivery.charge, Some(Price::default()));
+    assert_eq!(delivery.cash_on_delivery, Some(true));
+}
+
+#[test]
+fn serviceability_is_read_from_the_delivery_section() {
+    // other products on the page may not be deliverable
+    let html = fixture("delivery.html").replace(
+        "</body>",
+        "<div><div>Phone Y</div><div>Not deliverable</div></div></body>",
+    );
+    let url = Url::parse(PRODUCT_URL).unwrap();
+    let delivery = ProductDetails::from_html(url, &html)
+        .unwrap()
+        .delivery
+        .unwrap();
+    assert!(delivery.serviceable);
+}
+
+#[tokio::test]
+async fn pincode_shown_by_the_page_keeps_the_delivery() {
+    let stub = Stub::default().respond(200, fixture("delivery.html"));
+    let details = stub
+        .client()
+        .with_pincode("560001")
+        .product_by_pid("MOBX")
+        .await
+        .unwrap();
+
+    let requested = &stub.requests()[0];
+    assert!(requested
+        .query_pairs()
+        .any(|(key, value)| key == "pincode" && value == "560001"));
+    let delivery = details.delivery.unwrap();
+    assert_eq!(delivery.pincode.as_deref(), Some("560001"));
+
+    // the pincode is only sent, not reported back
+    let canonical = ProductRef::from_pid("MOBX").canonical_url();
+    assert_eq!(details.final_url, canonical.as_str());
+    assert_eq!(details.share_url, canonical.as_str());
+}
+
+#[tokio::test]
+async fn delivery_for_another_pincode_is_left_out() {
+    let stub = Stub::default().respond(200, fixture("delivery.html"));
+    let details = stub
+        .client()
+        .with_pincode("110001")
+        .product_by_pid("MOBX")
+        .await
+        .unwrap();
+    assert!(details.delivery.is_none());
+    assert!(!details.final_url.contains("pincode"));
+
+    let stub = Stub::default().respond(200, fixture("sellers.html"));
+    let client = stub.client().with_pincode("110001");
+    let sellers = client.sellers(&ProductRef::from_pid("MOBX")).await.unwrap();
+    assert_eq!(sellers.pincode.as_deref(), Some("560001"));
+    assert!(!sellers.is_empty());
+    assert!(sellers.iter().all(|listing| listing.delivery.is_none()));
+
+    // without a pincode the estimates are kept, whichever location they are for
+    let stub = Stub::default().respond(200, fixture("sellers.html"));
+    let sellers = stub
+        .client()
+        .sellers(&ProductRef::from_pid("MOBX"))
+        .await
+        .unwrap();
+    assert!(sellers[0].delivery.is_some());
+}
@@ -0,0 +1,233 @@
+mod common;
+
+use common::{fixture, Stub};
+use flipkart_scraper::{ErrorKind, Price, ProductDetails, Url};
+
+const PRODUCT_URL: &str = "https://www.flipkart.com/phone-x/p/itm1?pid=MOBX";
+
+/// Parses `product.html` as the page of the product `A`.
+fn parse_product_fixture() -> ProductDetails {
+    let url = Url::parse("https://www.flipkart.com/x/p/itm1?pid=A").unwrap();
+    ProductDetails::from_html(url, &fixture("product.html")).unwrap()
+}
+
+#[test]
+fn initial_state_is_read_from_the_main_product() {
+    let html = fixture("product_with_recommendations.html");
+    let details = ProductDetails::from_html(Url::parse(PRODUCT_URL).unwrap(), &html).unwrap();
+
+    assert_eq!(details.product_id.as_deref(), Some("MOBX"));
+    assert_eq!(details.brand.as_deref(), Some("Samsung"));
+    assert_eq!(details.current_price, Some(Price::from_rupees(9999)));
+    assert_eq!(details.original_price, Some(Price::from_rupees(12999)));
+    assert_eq!(details.rating, Some(4.3));
+    assert!(details.in_stock);
+    assert_eq!(
+        details.share_url,
+        "https://dl.flipkart.com/dl/phone-x/p/itm1?pid=MOBX&cmpid=product.share.pp"
+    );
+
+    let seller = details.seller.unwrap();
+    assert_eq!(seller.name, "RetailNet");
+    assert_eq!(seller.rating, Some(4.6));
+
+    let images: Vec<_> = details.thumbnails.iter().map(|image| &image.url).collect();
+    assert_eq!(
+        images,
+        [
+            "https://rukminim1.flixcart.com/image/128/128/a.jpeg?q=70",
+            "https://rukminim1.flixcart.com/image/128/128/b.jpeg?q=70",
+        ]
+    );
+
+    let summary = details.rating_summary.unwrap();
+    assert_eq!(summary.rating_count, Some(123456));
+    assert_eq!(summary.review_count, Some(12345));
+    let stars: Vec<_> = summary
+        .stars
+        .iter()
+        .map(|star| (star.stars, star.count))
+        .collect();
+    assert_eq!(
+        stars,
+        [(5, 81066), (4, 25000), (3, 8000), (2, 3000), (1, 6390)]
+    );
+
+    assert_eq!(details.specifications.len(), 1);
+    assert_eq!(details.specifications[0].category, "General");
+    assert_eq!(
+        details.specifications[0].specifications[0].value,
+        "Galaxy X"
+    );
+}
+
+#[test]
+fn initial_state_of_other_products_is_ignored() {
+    let html = fixture("product_with_recommendations.html");
+    // without a pid or a page context the main product can't be told apart
+    let url = Url::parse("https://www.flipkart.com/phone-x/p/itm1").unwrap();
+    let details = ProductDetails::from_html(url, &html).unwrap();
+
+    assert_eq!(details.product_id, None);
+    assert_eq!(details.current_price, None);
+    assert_eq!(details.rating, None);
+    assert!(details.seller.is_none());
+    assert!(details.thumbnails.is_empty());
+}
+
+#[tokio::test]
+async fn product_is_not_moved_by_recommendations() {
+    let client = Stub::default()
+        .respond(200, fixture("product_with_recommendations.html"))
+        .client();
+    let details = client.product_by_pid("MOBX").await.unwrap();
+    assert_eq!(details.product_id.as_deref(), Some("MOBX"));
+    assert!(!details.moved);
+}
+
+#[test]
+fn initial_state_falls_back_to_the_page_context() {
+    let html = r#"<html><body><h1>Phone X</h1><script>window.__INITIAL_STATE__ = {"alsoBought":[{"productId":"OTHERPID","pricing":{"finalPrice":{"value":199}}}],"pageDataV4":{"page":{"pageData":{"pageContext":{"productId":"MOBX","pricing":{"finalPrice":{"value":9999}}}}}}};</script></body></html>"#;
+    let url = Url::parse("https://www.flipkart.com/phone-x/p/itm1").unwrap();
+    let details = ProductDetails::from_html(url, html).unwrap();
+
+    assert_eq!(details.product_id.as_deref(), Some("MOBX"));
+    assert_eq!(details.current_price, Some(Price::from_rupees(9999)));
+}
+
+#[test]
+fn parses_product_fixture() {
+    let details = parse_product_fixture();
+
+    assert_eq!(details.name.as_deref(), Some("Phone X (Blue, 64 GB)"));
+    assert!(details.in_stock);
+    assert!(details.f_assured);
+    assert_eq!(details.rating, Some(4.3));
+    assert_eq!(details.current_price, Some(Price::from_rupees(9999)));
+    assert_eq!(details.original_price, Some(Price::from_rupees(12999)));
+    assert_eq!(
+        details.highlights,
+        ["4 GB RAM | 64 GB ROM", "6000 mAh Battery"]
+    );
+    assert_eq!(details.share_url, "https://www.flipkart.com/x/p/itm1?pid=A");
+
+    let seller = details.seller.unwrap();
+    assert_eq!(seller.name, "RetailNet");
+    assert_eq!(seller.rating, Some(4.6));
+
+    let images: Vec<_> = details.thumbnails.iter().map(|image| &image.url).collect();
+    assert_eq!(
+        images,
+        [
+            "https://rukminim2.flixcart.com/image/128/128/a.jpeg?q=70",
+            "https://rukminim2.flixcart.com/image/128/128/b.jpeg?q=70",
+        ]
+    );
+
+    let specifications = &details.specifications[0];
+    assert_eq!(specifications.category, "General");
+    assert_eq!(specifications.specifications[1].name, "Model Name");
+    assert_eq!(specifications.specifications[1].value, "Galaxy X");
+}
+
+#[test]
+fn parses_rating_summary() {
+    let summary = parse_product_fixture().rating_summary.unwrap();
+
+    assert_eq!(summary.rating_count, Some(123456));
+    assert_eq!(summary.review_count, Some(12345));
+    let stars: Vec<_> = summary
+        .stars
+        .iter()
+        .map(|star| (star.stars, star.count))
+        .collect();
+    assert_eq!(
+        stars,
+        [(5, 81066), (4, 25000), (3, 8000), (2, 3000), (1, 6390)]
+    );
+    let aspects: Vec<_> = summary
+        .aspects
+        .iter()
+        .map(|aspect| (aspect.name.as_str(), aspect.rating))
+        .collect();
+    assert_eq!(aspects, [("Camera", 4.2), ("Battery", 4.5)]);
+}
+
+#[test]
+fn parses_variants() {
+    let variants = parse_product_fixture().variants;
+    let options: Vec<_> = variants
+        .iter()
+        .flat_map(|group| {
+            group.options.iter().map(|option| {
+                let pid = option
+                    .product_ref
+                    .as_ref()
+                    .and_then(|product| product.pid.as_deref());
+                (
+                    group.dimension.as_str(),
+                    option.label.as_str(),
+                    pid,
+                    option.selected,
+                    option.in_stock,
+                )
+            })
+        })
+        .collect();
+    assert_eq!(
+        options,
+        [
+            ("Color", "Blue", Some("A"), true, true),
+            ("Color", "Black", Some("B"), false, true),
+            ("Storage", "64 GB", Some("A"), true, true),
+            ("Storage", "128 GB", None, false, false),
+        ]
+    );
+    assert_eq!(
+        variants[0].options[1].url.as_deref(),
+        Some("https://www.flipkart.com/x/p/itm1?pid=B")
+    );
+}
+
+#[test]
+fn parses_breadcrumbs_and_brand() {
+    let details = parse_product_fixture();
+    // the last level is the product itself
+    let breadcrumbs: Vec<_> = details
+        .breadcrumbs
+        .iter()
+        .map(|level| (level.name.as_str(), level.url.as_deref()))
+        .collect();
+    assert_eq!(
+        breadcrumbs,
+        [
+            ("Home", Some("https://www.flipkart.com/")),
+            (
+                "Mobiles & Accessories",
+                Some("https://www.flipkart.com/mobiles-accessories-store")
+            ),
+            ("Mobiles", Some("https://www.flipkart.com/mobiles-store")),
+        ]
+    );
+    assert_eq!(details.brand.as_deref(), Some("Samsung"));
+}
+
+#[test]
+fn reports_missing_and_error_pages() {
+    let url = Url::parse(PRODUCT_URL).unwrap();
+    let parse = |body: &str| {
+        ProductDetails::from_html(url.clone(), body)
+            .unwrap_err()
+            .kind()
+    };
+
+    assert_eq!(
+        parse("<html><body>The page you are looking for has been moved or deleted</body></html>"),
+        ErrorKind::ProductNotFound
+    );
+    assert_eq!(
+        parse("<html><body>Internal Server Error</body></html>"),
+        ErrorKind::Blocked
+    );
+    assert_eq!(parse("<html><body></body></html>"), ErrorKind::Parse);
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::fixture;
+use flipkart_scraper::{reviews::ReviewsQuery, ErrorKind, ProductRef, ProductReviews, Url};
+
+#[test]
+fn parses_reviews() {
+    let query = ReviewsQuery::new(ProductRef::from_pid("MOBX"));
+    let reviews = ProductReviews::from_html(query, &fixture("reviews.html")).unwrap();
+
+    assert_eq!(reviews.product.pid.as_deref(), Some("MOBX"));
+    assert_eq!(reviews.total_pages, Some(120));
+    // rating summary at the top of the page is not a review
+    assert_eq!(reviews.len(), 2);
+
+    let review = &reviews[0];
+    assert_eq!(review.title.as_deref(), Some("Terrific"));
+    assert_eq!(review.body, "Great phone, battery lasts.");
+    assert_eq!(review.rating, Some(5));
+    assert_eq!(review.author.as_deref(), Some("Ravi Kumar"));
+    assert!(review.certified_buyer);
+    assert_eq!(review.location.as_deref(), Some("Mumbai District"));
+    assert_eq!(review.date.as_deref(), Some("Mar, 2023"));
+    assert_eq!(review.helpful_votes, Some(1234));
+    assert_eq!(review.unhelpful_votes, Some(56));
+
+    let review = &reviews[1];
+    assert_eq!(review.title.as_deref(), Some("Bad"));
+    assert_eq!(review.body, "Meh.");
+    assert_eq!(review.rating, Some(2));
+    assert_eq!(review.author.as_deref(), Some("Anon"));
+    assert!(!review.certified_buyer);
+    assert_eq!(review.location, None);
+    assert_eq!(review.date.as_deref(), Some("10 months ago"));
+    assert_eq!(review.helpful_votes, None);
+}
+
+#[tokio::test]
+async fn rejects_links_without_a_product() {
+    let url = Url::parse("https://www.flipkart.com/search?q=phone").unwrap();
+    let error = ProductReviews::fetch(url).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Parse);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::fixture;
+use flipkart_scraper::{search::SearchQuery, Price, ProductSearch};
+
+fn search(name: &str) -> ProductSearch {
+    ProductSearch::from_html(SearchQuery::new("phone"), &fixture(name)).unwrap()
+}
+
+#[test]
+fn parses_search_results() {
+    let search = search("search.html");
+    assert_eq!(search.query, "phone");
+    assert_eq!(search.total_pages, Some(12));
+    assert_eq!(search.len(), 2);
+
+    let first = &search[0];
+    assert_eq!(first.product_name, "Phone X");
+    assert_eq!(first.product_link, "https://flipkart.com/x/p/itm1?pid=MOB1");
+    let product = first.product_ref.as_ref().unwrap();
+    assert_eq!(product.pid.as_deref(), Some("MOB1"));
+    assert_eq!(first.thumbnail.url, "t.jpg");
+    assert_eq!(first.current_price, Some(Price::from_rupees(9999)));
+    assert_eq!(first.original_price, Some(Price::from_rupees(12999)));
+}
+
+#[test]
+fn parses_ratings_and_their_counts() {
+    let search = search("search.html");
+
+    let first = &search[0];
+    assert_eq!(first.rating, Some(4.3));
+    assert_eq!(first.rating_count, Some(12345));
+    assert_eq!(first.review_count, Some(1234));
+
+    // listings with few ratings only show their count
+    let second = &search[1];
+    assert_eq!(second.rating, Some(4.1));
+    assert_eq!(second.rating_count, Some(1024));
+    assert_eq!(second.review_count, None);
+    assert_eq!(second.original_price, None);
+}
+
+#[test]
+fn flags_sponsored_assured_and_discounted_results() {
+    let search = search("search.html");
+
+    let sponsored = &search[0];
+    assert_eq!(sponsored.product_name, "Phone X");
+    assert!(sponsored.sponsored);
+    assert!(!sponsored.f_assured);
+    assert_eq!(sponsored.discount_percent, Some(23));
+
+    let organic = &search[1];
+    assert!(!organic.sponsored);
+    assert!(organic.f_assured);
+    assert_eq!(organic.discount_percent, None);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::fixture;
+use flipkart_scraper::{ErrorKind, Price, ProductRef, ProductSellers, Url};
+
+#[test]
+fn parses_every_seller() {
+    let product = ProductRef::from_pid("MOBX");
+    let sellers = ProductSellers::from_html(product, &fixture("sellers.html")).unwrap();
+
+    assert_eq!(sellers.product.pid.as_deref(), Some("MOBX"));
+    assert_eq!(sellers.pincode.as_deref(), Some("560001"));
+    let names: Vec<_> = sellers
+        .iter()
+        .map(|listing| (listing.seller.name.as_str(), listing.seller.rating))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            ("RetailNet", Some(4.6)),
+            ("SuperComNet", Some(4.1)),
+            ("New Seller", Some(3.2)),
+        ]
+    );
+
+    let listing = &sellers[0];
+    assert_eq!(listing.current_price, Some(Price::from_rupees(9999)));
+    assert_eq!(listing.original_price, Some(Price::from_rupees(12999)));
+    assert_eq!(
+        listing.delivery.as_deref(),
+        Some("Delivery by 12 Oct, Thursday")
+    );
+    assert!(listing.f_assured);
+
+    let listing = &sellers[1];
+    assert_eq!(listing.current_price, Some(Price::from_rupees(10499)));
+    assert_eq!(listing.original_price, None);
+    assert_eq!(listing.delivery.as_deref(), Some("Delivery in 5 days"));
+    assert!(!listing.f_assured);
+
+    assert_eq!(sellers[2].delivery, None);
+}
+
+#[test]
+fn reports_missing_products() {
+    let body = "<html><body>The page you are looking for has been moved or deleted</body></html>";
+    let error = ProductSellers::from_html(ProductRef::from_pid("MOBX"), body).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::ProductNotFound);
+}
+
+#[tokio::test]
+async fn rejects_links_without_a_product() {
+    let url = Url::parse("https://www.flipkart.com/mobiles-store").unwrap();
+    let error = ProductSellers::fetch(url).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Parse);
+}